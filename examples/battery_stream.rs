@@ -0,0 +1,27 @@
+use futures_util::StreamExt;
+use seersdk_rs::{BatteryStatusRequest, RbkClient};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    let rbk_client = RbkClient::new("192.168.8.114");
+
+    // Query the battery level once per second over the shared connection
+    let stream = rbk_client.poll(
+        BatteryStatusRequest::new(),
+        Duration::from_secs(1),
+        Duration::from_secs(5),
+    );
+    let mut stream = std::pin::pin!(stream);
+
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(status) => println!(
+                "Battery: {:.0}% (charging: {})",
+                status.battery_level * 100.0,
+                status.charging
+            ),
+            Err(e) => println!("Battery query failed: {}", e),
+        }
+    }
+}
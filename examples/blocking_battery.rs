@@ -0,0 +1,25 @@
+//! Query the battery without managing a tokio runtime
+//!
+//! Run with `cargo run --example blocking_battery --features blocking`
+
+use seersdk_rs::{BatteryStatusRequest, BlockingRbkClient};
+use std::time::Duration;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Spins up a dedicated runtime thread in the background
+    let rbk_client = BlockingRbkClient::new("192.168.8.114")?;
+
+    for _ in 0..5 {
+        let status = rbk_client
+            .request(BatteryStatusRequest::new(), Duration::from_secs(10))?;
+        println!(
+            "Battery: {:.0}% (charging: {})",
+            status.battery_level * 100.0,
+            status.charging
+        );
+
+        std::thread::sleep(Duration::from_secs(1));
+    }
+
+    Ok(())
+}
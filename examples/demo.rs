@@ -84,7 +84,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "│ ✓ Total Time:    {:<38.1}s │",
                 info.total_time_ms / 1000.0
             );
-            println!("│ ✓ Controller:    {:<38.1}°C│", info.controller_temp);
+            if let Some(temp) = info.controller_temp {
+                println!("│ ✓ Controller:    {:<38.1}°C│", temp);
+            }
         }
         Err(e) => println!("│ ✗ Failed: {:<43} │", e),
     }
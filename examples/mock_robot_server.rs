@@ -57,7 +57,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::RwLock;
@@ -77,8 +77,8 @@ struct Waypoint {
 
 /// Shared application state
 struct AppState {
-    robot: Arc<RwLock<RobotState>>,
     waypoints: Arc<RwLock<HashMap<String, Waypoint>>>,
+    robot: Arc<RwLock<RobotState>>,
 }
 
 /// Navigation task item
@@ -105,11 +105,13 @@ struct RobotState {
     y: f64,
     angle: f64,
     confidence: f64,
+    reloc_status: u32,
 
     // Battery
     battery_level: f64,
     battery_temp: f64,
     charging: bool,
+    soft_emc: bool,
     voltage: f64,
     current: f64,
 
@@ -131,13 +133,75 @@ struct RobotState {
     jack_height: f64,
     jack_has_payload: bool,
     jack_enabled: bool,
+    // Running jack command (rising, start time), the jack is in place after
+    // 300 ms
+    jack_motion: Option<(bool, Instant)>,
+
+    // Fork
+    fork_height: f64,
+
+    // Digital I/O levels, indexed by port id
+    digital_inputs: Vec<bool>,
+    digital_outputs: Vec<bool>,
+
+    // Robot params by plugin, as in effect and as saved to the config
+    params: serde_json::Value,
+    saved_params: serde_json::Value,
+
+    // Files stored on the robot, name and size in bytes
+    files: Vec<(String, u64)>,
 
     // Odometry
     mileage: f64,
+    /// Uptime since the mock started, in milliseconds
+    session_time: f64,
+    /// Uptime over the robot's whole life, in milliseconds
     total_time: f64,
 
     // Map
     current_map: String,
+
+    // Control lock holder (nick_name, ip)
+    lock_owner: Option<(String, String)>,
+
+    // Simulated Modbus register file
+    modbus_registers: HashMap<u32, u16>,
+
+    // Opaque bytes stored by UpdateTransparentData (6900)
+    transparent_data: Vec<u8>,
+
+    // Robot model file, downloaded by 1500 and replaced by 4200
+    model_file: Vec<u8>,
+
+    // Alarms raised by third-party systems (4800/4802), as JSON objects
+    errors: Vec<serde_json::Value>,
+    warnings: Vec<serde_json::Value>,
+
+    // Joint positions of the robotic arm in radians
+    arm_joints: Vec<f64>,
+
+    // Audio file being played by PlayAudio (6000)
+    playing_audio: Option<String>,
+
+    // Scripts stored on the robot (name -> content)
+    scripts: HashMap<String, String>,
+
+    // Running calibration (type, start time) and the last finished one
+    calibration: Option<(u32, Instant)>,
+    last_calibration: Option<u32>,
+
+    // Motor encoder counts, zeroed by ClearMotorEncoder (2024)
+    encoders: Vec<i64>,
+
+    // Goods bound to containers (container -> goods)
+    container_goods: HashMap<String, String>,
+
+    // Push subscription declared by ConfigurePush (4091)
+    push_apis: Vec<u16>,
+    push_interval_ms: u32,
+
+    // API number of every request received, in arrival order
+    received_apis: Vec<u16>,
 }
 
 impl Default for RobotState {
@@ -151,10 +215,12 @@ impl Default for RobotState {
             y: 0.0,
             angle: 0.0,
             confidence: 0.98,
+            reloc_status: 1, // Success
 
             battery_level: 0.85,
             battery_temp: 25.0,
             charging: false,
+            soft_emc: false,
             voltage: 48.0,
             current: 2.5,
 
@@ -172,11 +238,56 @@ impl Default for RobotState {
             jack_height: 0.0,
             jack_has_payload: false,
             jack_enabled: true,
+            jack_motion: None,
+            fork_height: 0.0,
+            digital_inputs: vec![false; 8],
+            digital_outputs: vec![false; 8],
+            files: vec![
+                ("robot.log".to_string(), 20_480),
+                ("model.model".to_string(), 4_096),
+            ],
+            params: default_params(),
+            saved_params: default_params(),
 
             mileage: 1234.56,
+            session_time: 0.0,
             total_time: 3600000.0,
 
             current_map: "default_map".to_string(),
+
+            lock_owner: None,
+
+            modbus_registers: HashMap::new(),
+
+            transparent_data: Vec::new(),
+
+            model_file: json!({
+                "contour": [[0.5, 0.3], [-0.5, 0.3], [-0.5, -0.3], [0.5, -0.3]],
+                "name": "mock_robot"
+            })
+            .to_string()
+            .into_bytes(),
+
+            errors: Vec::new(),
+            warnings: Vec::new(),
+
+            arm_joints: vec![0.0; 6],
+
+            playing_audio: None,
+
+            scripts: HashMap::new(),
+
+            calibration: None,
+            last_calibration: None,
+
+            encoders: vec![15230, 15188],
+
+            container_goods: HashMap::new(),
+
+            push_apis: Vec::new(),
+            push_interval_ms: 1000,
+
+            received_apis: Vec::new(),
         }
     }
 }
@@ -187,11 +298,11 @@ struct RbkFrame {
     flow_no: u16,
     api_no: u16,
     body: String,
+    raw_body: Vec<u8>,
 }
 
 /// Encode RBK response
-fn encode_response(api_no: u16, body_str: &str, flow_no: u16) -> BytesMut {
-    let body_bytes = body_str.as_bytes();
+fn encode_response(api_no: u16, body_bytes: &[u8], flow_no: u16) -> BytesMut {
     let body_len = body_bytes.len() as u32;
     let head_size = 16;
 
@@ -255,17 +366,17 @@ impl RbkDecoder {
             return None;
         }
 
-        let body = if self.body_size == 0 {
-            String::new()
+        let raw_body = if self.body_size == 0 {
+            Vec::new()
         } else {
-            let body_bytes = buf.split_to(self.body_size as usize);
-            String::from_utf8_lossy(&body_bytes).to_string()
+            buf.split_to(self.body_size as usize).to_vec()
         };
 
         let frame = RbkFrame {
             flow_no: self.flow_no,
             api_no: self.api_no,
-            body,
+            body: String::from_utf8_lossy(&raw_body).to_string(),
+            raw_body,
         };
 
         self.started = false;
@@ -286,11 +397,169 @@ fn get_timestamp() -> String {
     format!("{}", now)
 }
 
+/// `status` with a success `ret_code` and empty `err_msg` added
+fn with_status(mut status: serde_json::Value) -> serde_json::Value {
+    status["ret_code"] = json!(0);
+    status["err_msg"] = json!("");
+    status
+}
+
+/// Sections of a combined status merged into one flat object
+fn merge_status<const N: usize>(
+    sections: [serde_json::Value; N],
+) -> serde_json::Value {
+    let mut all = serde_json::Map::new();
+    for section in sections {
+        if let serde_json::Value::Object(fields) = section {
+            all.extend(fields);
+        }
+    }
+    serde_json::Value::Object(all)
+}
+
+fn pose_status(s: &RobotState) -> serde_json::Value {
+    json!({
+        "x": s.x,
+        "y": s.y,
+        "angle": s.angle,
+        "confidence": s.confidence
+    })
+}
+
+fn speed_status() -> serde_json::Value {
+    json!({
+        "vx": 0.5,
+        "vy": 0.0,
+        "w": 0.1
+    })
+}
+
+fn block_status(s: &RobotState) -> serde_json::Value {
+    json!({
+        "blocked": s.is_blocked,
+        "block_reason": s.block_reason,
+        "block_x": null,
+        "block_y": null
+    })
+}
+
+fn container_status(s: &RobotState) -> serde_json::Value {
+    s.container_goods
+        .iter()
+        .map(|(container, goods)| {
+            json!({
+                "container_name": container,
+                "goods_id": goods
+            })
+        })
+        .collect()
+}
+
+fn battery_status(s: &RobotState) -> serde_json::Value {
+    json!({
+        "battery_level": s.battery_level,
+        "battery_temp": s.battery_temp,
+        "charging": s.charging,
+        "voltage": s.voltage,
+        "current": s.current
+    })
+}
+
+/// Area section, with the speed limit set for the area through the
+/// `AreaSpeedLimit` params
+fn area_status(s: &RobotState) -> serde_json::Value {
+    let area = "mock_area";
+    let mut status = json!({ "area_ids": [area] });
+    if let Some(max_speed) = s.params["AreaSpeedLimit"][area].as_f64() {
+        status["max_speed"] = json!(max_speed);
+    }
+    status
+}
+
+/// Navigation status, with the stations passed and ahead derived from the
+/// task queue
+fn nav_status(s: &RobotState) -> serde_json::Value {
+    let finished_path: Vec<&str> = s
+        .task_queue
+        .iter()
+        .take(s.current_task_index)
+        .map(|t| t.target.as_str())
+        .collect();
+    let unfinished_path: Vec<&str> = s
+        .task_queue
+        .iter()
+        .skip(s.current_task_index + 1)
+        .map(|t| t.target.as_str())
+        .collect();
+
+    json!({
+        "task_status": s.nav_status,
+        "task_type": s.nav_type,
+        "target_id": s.target_id,
+        "target_point": s.target_point,
+        "finished_path": finished_path,
+        "unfinished_path": unfinished_path,
+        "move_status_info": "Mock navigation running"
+    })
+}
+
+/// Jack section of the jack and combined peripheral status
+fn jack_status(s: &RobotState) -> serde_json::Value {
+    // The payload only changes once the jack is in place
+    let (jack_state, has_payload) = match s.jack_motion {
+        Some((rising, started)) if started.elapsed().as_millis() < 300 => {
+            if rising {
+                (0, false)
+            } else {
+                (2, true)
+            }
+        }
+        Some((true, _)) => (1, s.jack_has_payload),
+        Some((false, _)) => (3, s.jack_has_payload),
+        None => (4, s.jack_has_payload),
+    };
+    json!({
+        "jack_mode": true,
+        "jack_enable": s.jack_enabled,
+        "jack_error_code": 0,
+        "jack_state": jack_state,
+        "jack_isFull": has_payload,
+        "jack_speed": 0,
+        "jack_emc": false,
+        "jack_height": s.jack_height,
+        "peripheral_data": []
+    })
+}
+
+/// Fork section of the fork and combined peripheral status
+fn fork_status(s: &RobotState) -> serde_json::Value {
+    json!({
+        "fork_height": s.fork_height,
+        "fork_height_in_place": true,
+        "fork_auto_flag": true,
+        "forward_val": 0.0,
+        "forward_in_place": true,
+        "fork_pressure_actual": 0.0
+    })
+}
+
+/// Digital I/O levels in the layout of the combined peripheral status
+fn digital_io(levels: &[bool]) -> Vec<serde_json::Value> {
+    levels
+        .iter()
+        .enumerate()
+        .map(|(id, level)| {
+            json!({ "id": id, "source": "normal", "status": level, "valid": true })
+        })
+        .collect()
+}
+
 /// Handle API request and generate response
 async fn handle_request(
     state: Arc<RwLock<RobotState>>,
     waypoints: Arc<RwLock<HashMap<String, Waypoint>>>,
     frame: RbkFrame,
+    peer_ip: &str,
 ) -> String {
     let api_no = frame.api_no;
 
@@ -313,7 +582,7 @@ async fn handle_request(
             let s = state.read().await;
             json!({
                 "odo": s.mileage,
-                "total": s.total_time,
+                "total": s.session_time,
                 "total_time": s.total_time,
                 "controller_temp": 35.5,
                 "controller_humi": 45.0,
@@ -326,106 +595,410 @@ async fn handle_request(
         1004 => {
             // RobotPose - Location
             let s = state.read().await;
+            with_status(pose_status(&s)).to_string()
+        }
+        1005 => {
+            // RobotSpeed
+            with_status(speed_status()).to_string()
+        }
+        1006 => {
+            // BlockStatus
+            let s = state.read().await;
+            with_status(block_status(&s)).to_string()
+        }
+        1007 => {
+            // BatteryStatus
+            let s = state.read().await;
+            with_status(battery_status(&s)).to_string()
+        }
+        1011 => {
+            // Areas the robot is in
+            let s = state.read().await;
+            with_status(area_status(&s)).to_string()
+        }
+        1009 => {
+            // Laser scan, a synthetic half circle at 2 m with 1 degree steps
+            let angle_increment = std::f64::consts::PI / 180.0;
+            let ranges: Vec<f64> = (0..=180)
+                .map(|i| 2.0 + 0.1 * ((i as f64) * angle_increment).sin())
+                .collect();
+            let intensities = vec![100.0; ranges.len()];
             json!({
-                "x": s.x,
-                "y": s.y,
-                "angle": s.angle,
-                "confidence": s.confidence,
+                "angle_min": -std::f64::consts::FRAC_PI_2,
+                "angle_max": std::f64::consts::FRAC_PI_2,
+                "angle_increment": angle_increment,
+                "ranges": ranges,
+                "intensities": intensities,
                 "ret_code": 0,
-                "err_msg": ""
+                "err_msg": "",
+                "create_on": get_timestamp()
             })
             .to_string()
         }
-        1005 => {
-            // RobotSpeed
+        1012 => {
+            // Emergency stop status
+            let s = state.read().await;
+            json!({
+                "emergency": false,
+                "driver_emc": false,
+                "soft_emc": s.soft_emc,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1014 => {
+            // IMU, level and at rest apart from gravity
+            let s = state.read().await;
+            json!({
+                "yaw": s.angle,
+                "roll": 0.0,
+                "pitch": 0.0,
+                "acc_x": 0.0,
+                "acc_y": 0.0,
+                "acc_z": 9.81,
+                "rot_x": 0.0,
+                "rot_y": 0.0,
+                "rot_z": 0.0,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1018 => {
+            // Motor encoders
+            let s = state.read().await;
+            let encoders: Vec<serde_json::Value> = s
+                .encoders
+                .iter()
+                .enumerate()
+                .map(|(id, value)| {
+                    json!({
+                        "id": id,
+                        "value": value,
+                        "speed": 0.0
+                    })
+                })
+                .collect();
+            json!({
+                "encoders": encoders,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1020 => {
+            // NavStatus
+            let s = state.read().await;
+            let mut status = with_status(nav_status(&s));
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1027 => {
+            // JackStatus
+            let s = state.read().await;
+            let mut status = jack_status(&s);
+            status["ret_code"] = json!(0);
+            status["err_msg"] = json!("");
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1040 => {
+            // Motor status, one drive motor per encoder
+            let s = state.read().await;
+            let motors: Vec<serde_json::Value> = s
+                .encoders
+                .iter()
+                .enumerate()
+                .map(|(id, position)| {
+                    json!({
+                        "motor_name": format!("motor{}", id),
+                        "position": position,
+                        "speed": 0.0,
+                        "current": 0.5,
+                        "voltage": 48.0,
+                        "temperature": 35.0,
+                        "error_code": 0
+                    })
+                })
+                .collect();
+            json!({
+                "motor_info": motors,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1028 => {
+            // ForkStatus
+            let s = state.read().await;
+            let mut status = fork_status(&s);
+            status["ret_code"] = json!(0);
+            status["err_msg"] = json!("");
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1100 => {
+            // All status 1: pose, battery and block flattened into one object
+            let s = state.read().await;
+            let mut status = with_status(merge_status([
+                pose_status(&s),
+                battery_status(&s),
+                block_status(&s),
+            ]));
+            status["containers"] = container_status(&s);
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1101 => {
+            // All status 2: speed, navigation and area flattened into one
+            // object
+            let s = state.read().await;
+            let mut status = with_status(merge_status([
+                speed_status(),
+                nav_status(&s),
+                area_status(&s),
+            ]));
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1102 => {
+            // All status 3: jack, fork and I/O flattened into one object
+            let s = state.read().await;
+            let mut status = jack_status(&s);
+            if let (Some(all), serde_json::Value::Object(fork)) =
+                (status.as_object_mut(), fork_status(&s))
+            {
+                all.extend(fork);
+            }
+            status["DI"] = json!(digital_io(&s.digital_inputs));
+            status["DO"] = json!(digital_io(&s.digital_outputs));
+            status["ret_code"] = json!(0);
+            status["err_msg"] = json!("");
+            status["create_on"] = json!(get_timestamp());
+            status.to_string()
+        }
+        1050 => {
+            // Alarm status
+            let s = state.read().await;
+            json!({
+                "fatals": [],
+                "errors": s.errors,
+                "warnings": s.warnings,
+                "notices": [],
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1060 => {
+            // CurrentLock
+            let s = state.read().await;
+            let (nick_name, ip) = s.lock_owner.clone().unwrap_or_default();
             json!({
-                "vx": 0.5,
-                "vy": 0.0,
-                "w": 0.1,
+                "locked": s.lock_owner.is_some(),
+                "nick_name": nick_name,
+                "ip": ip,
                 "ret_code": 0,
                 "err_msg": ""
             })
             .to_string()
         }
-        1006 => {
-            // BlockStatus
+        1071 => {
+            // Read Modbus registers
             let s = state.read().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let addr = req.get("addr").and_then(|v| v.as_u64()).unwrap_or(0);
+            let count = req.get("count").and_then(|v| v.as_u64()).unwrap_or(0);
+            let values: Vec<u16> = (addr..addr + count)
+                .map(|a| {
+                    s.modbus_registers.get(&(a as u32)).copied().unwrap_or(0)
+                })
+                .collect();
             json!({
-                "blocked": s.is_blocked,
-                "block_reason": s.block_reason,
-                "block_x": null,
-                "block_y": null,
+                "values": values,
                 "ret_code": 0,
                 "err_msg": ""
             })
             .to_string()
         }
-        1007 => {
-            // BatteryStatus
+        1506 => {
+            // Script list
             let s = state.read().await;
+            let mut scripts: Vec<&String> = s.scripts.keys().collect();
+            scripts.sort();
+            json!({
+                "scripts": scripts,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1510 => {
+            // Calibration status, a calibration finishes after 300 ms
+            let mut s = state.write().await;
+            if let Some((calib_type, started)) = s.calibration
+                && started.elapsed().as_millis() >= 300
+            {
+                s.calibration = None;
+                s.last_calibration = Some(calib_type);
+            }
+            let (calib_state, calib_type) =
+                match (s.calibration, s.last_calibration) {
+                    (Some((calib_type, _)), _) => (1, Some(calib_type)),
+                    (None, Some(calib_type)) => (2, Some(calib_type)),
+                    (None, None) => (0, None),
+                };
             json!({
-                "battery_level": s.battery_level,
-                "battery_temp": s.battery_temp,
-                "charging": s.charging,
-                "voltage": s.voltage,
-                "current": s.current,
+                "state": calib_state,
+                "calib_type": calib_type,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1760 => {
+            // GNSS status
+            json!({
+                "connected": true,
+                "fix_type": 4,
+                "satellites": 12,
+                "lat": 53.9006,
+                "lon": 27.559,
                 "ret_code": 0,
                 "err_msg": ""
             })
             .to_string()
         }
-        1020 => {
-            // NavStatus
+        1761 => {
+            // GNSS device list
+            json!({
+                "devices": ["gnss0"],
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        1798 => {
+            // Robot file list
             let s = state.read().await;
-
-            // Build finished/unfinished paths based on task queue
-            let finished_path: Vec<String> = if s.task_queue.is_empty() {
-                vec![]
-            } else {
-                s.task_queue
-                    .iter()
-                    .take(s.current_task_index)
-                    .map(|t| t.target.clone())
-                    .collect()
-            };
-
-            let unfinished_path: Vec<String> = if s.task_queue.is_empty() {
-                vec![]
-            } else {
-                s.task_queue
-                    .iter()
-                    .skip(s.current_task_index + 1)
-                    .map(|t| t.target.clone())
-                    .collect()
-            };
-
+            let files: Vec<_> = s
+                .files
+                .iter()
+                .map(|(name, size)| json!({"name": name, "size": size}))
+                .collect();
             json!({
-                "task_status": s.nav_status,
-                "task_type": s.nav_type,
-                "target_id": s.target_id,
-                "target_point": s.target_point,
-                "finished_path": finished_path,
-                "unfinished_path": unfinished_path,
-                "move_status_info": "Mock navigation running",
+                "files": files,
                 "ret_code": 0,
-                "create_on": get_timestamp(),
                 "err_msg": ""
             })
             .to_string()
         }
-        1027 => {
-            // JackStatus
+        1021 => {
+            // Relocation status
+            let s = state.read().await;
+            json!({
+                "reloc_status": s.reloc_status,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1665 => {
+            // 3D QR tags seen during mapping
+            json!({
+                "tags": [
+                    {
+                        "id": "tag_1",
+                        "x": 1.2,
+                        "y": 0.4,
+                        "z": 0.8,
+                        "confidence": 0.93
+                    }
+                ],
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1669 => {
+            // Robotic arm status
+            let s = state.read().await;
+            json!({
+                "joints": s.arm_joints,
+                "moving": false,
+                "error_code": 0,
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1673 => {
+            // Robotic arm motion, reaches the target instantly
+            let mut s = state.write().await;
+            let joints = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .ok()
+                .and_then(|req| {
+                    serde_json::from_value::<Vec<f64>>(
+                        req.get("joints")?.clone(),
+                    )
+                    .ok()
+                });
+            match joints {
+                Some(joints) => {
+                    s.arm_joints = joints;
+                    json!({
+                        "ret_code": 0,
+                        "err_msg": "Arm moved"
+                    })
+                    .to_string()
+                }
+                None => json!({
+                    "ret_code": 40001,
+                    "err_msg": "Missing joints"
+                })
+                .to_string(),
+            }
+        }
+        1400 => {
+            // Robot params by plugin
+            let s = state.read().await;
+            let mut params = s.params.clone();
+            params["ret_code"] = json!(0);
+            params["err_msg"] = json!("");
+            params.to_string()
+        }
+        1803 => {
+            // Storage bins seen by the robot
+            json!({
+                "bins": [
+                    {"bin_id": "bin_01", "filled": true},
+                    {"bin_id": "bin_02", "filled": false}
+                ],
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        1850 => {
+            // Sound status
             let s = state.read().await;
             json!({
-                "jack_mode": true,
-                "jack_enable": s.jack_enabled,
-                "jack_error_code": 0,
-                "jack_state": 4,
-                "jack_isFull": s.jack_has_payload,
-                "jack_speed": 0,
-                "jack_emc": false,
-                "jack_height": s.jack_height,
-                "peripheral_data": [],
+                "playing": s.playing_audio.is_some(),
+                "current": s.playing_audio,
+                "volume": 0.8,
                 "ret_code": 0,
                 "err_msg": "",
                 "create_on": get_timestamp()
@@ -598,7 +1171,10 @@ async fn handle_request(
             .to_string()
         }
         2002 => {
-            // Relocation
+            // Relocation, completes immediately and awaits confirmation
+            let mut s = state.write().await;
+            s.reloc_status = 3; // Completed
+            s.confidence = 0.0;
             json!({
                 "ret_code": 0,
                 "err_msg": "Relocation initiated"
@@ -607,23 +1183,46 @@ async fn handle_request(
         }
         2003 => {
             // Confirm location
+            let mut s = state.write().await;
+            if s.reloc_status == 3 {
+                s.reloc_status = 1; // Success
+                s.confidence = 0.98;
+            }
             json!({
                 "ret_code": 0,
                 "err_msg": "Location confirmed"
             })
             .to_string()
         }
+        2004 => {
+            // Cancel relocation, back to the state before relocating
+            let mut s = state.write().await;
+            if s.reloc_status == 2 || s.reloc_status == 3 {
+                s.reloc_status = 0;
+            }
+            json!({
+                "ret_code": 0,
+                "err_msg": "Relocation cancelled"
+            })
+            .to_string()
+        }
+        2010 => {
+            // Open loop motion
+            json!({
+                "ret_code": 0,
+                "err_msg": "Motion updated"
+            })
+            .to_string()
+        }
         2022 => {
             // Switch map
             let mut s = state.write().await;
             if let Ok(req) =
                 serde_json::from_str::<serde_json::Value>(&frame.body)
-            {
-                if let Some(map_name) =
+                && let Some(map_name) =
                     req.get("map_name").and_then(|v| v.as_str())
-                {
-                    s.current_map = map_name.to_string();
-                }
+            {
+                s.current_map = map_name.to_string();
             }
             json!({
                 "ret_code": 0,
@@ -631,6 +1230,24 @@ async fn handle_request(
             })
             .to_string()
         }
+        2024 => {
+            // Clear motor encoders
+            let mut s = state.write().await;
+            s.encoders.iter_mut().for_each(|value| *value = 0);
+            json!({
+                "ret_code": 0,
+                "err_msg": "Motor encoders cleared"
+            })
+            .to_string()
+        }
+        2026 => {
+            // Clear weight sensor value
+            json!({
+                "ret_code": 0,
+                "err_msg": "Weight sensor cleared"
+            })
+            .to_string()
+        }
 
         // Navigation APIs (3000-3999)
         3001 => {
@@ -671,22 +1288,127 @@ async fn handle_request(
 
             if let Ok(req) =
                 serde_json::from_str::<serde_json::Value>(&frame.body)
+                && let Some(target) = req.get("id").and_then(|v| v.as_str())
             {
-                if let Some(target) = req.get("id").and_then(|v| v.as_str()) {
-                    // Clear old task queue - starting new navigation
-                    s.task_queue.clear();
-                    s.current_task_index = 0;
+                // Clear old task queue - starting new navigation
+                s.task_queue.clear();
+                s.current_task_index = 0;
+
+                let start = req
+                    .get("source_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("SELF_POSITION");
+
+                let task_id = req
+                    .get("task_id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "single_task".to_string());
+
+                // Get positions from waypoints
+                let start_pos = if start == "SELF_POSITION" {
+                    [s.x, s.y, s.angle]
+                } else {
+                    wp.get(start)
+                        .map(|w| [w.x, w.y, 0.0])
+                        .unwrap_or([s.x, s.y, s.angle])
+                };
 
-                    let start = req
-                        .get("source_id")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("SELF_POSITION");
+                let target_pos = wp
+                    .get(target)
+                    .map(|w| [w.x, w.y, 0.0])
+                    .unwrap_or([start_pos[0] + 5.0, start_pos[1] + 5.0, 0.0]);
+
+                // Create single task
+                s.task_queue.push(NavTask {
+                    task_id,
+                    start: start.to_string(),
+                    target: target.to_string(),
+                    start_pos,
+                    target_pos,
+                    status: 2, // Running
+                });
+
+                s.nav_status = 2; // Running
+                s.nav_type = 3; // Path nav
+                s.target_id = target.to_string();
+                s.target_point = target_pos;
+            }
+
+            json!({
+                "ret_code": 0,
+                "err_msg": "Navigation started",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        3053 => {
+            // Target path, a straight line from the start to the target
+            let s = state.read().await;
+            let wp = waypoints.read().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+
+            let start_pos = req
+                .get("source_id")
+                .and_then(|v| v.as_str())
+                .and_then(|id| wp.get(id))
+                .map(|w| [w.x, w.y, 0.0])
+                .unwrap_or([s.x, s.y, s.angle]);
+            let target_pos = req
+                .get("id")
+                .and_then(|v| v.as_str())
+                .and_then(|id| wp.get(id))
+                .map(|w| [w.x, w.y, 0.0])
+                .unwrap_or([start_pos[0] + 5.0, start_pos[1] + 5.0, 0.0]);
+
+            let dx = target_pos[0] - start_pos[0];
+            let dy = target_pos[1] - start_pos[1];
+            let heading = dy.atan2(dx);
+            let steps = 10;
+            let points: Vec<[f64; 3]> = (0..=steps)
+                .map(|i| {
+                    let t = i as f64 / steps as f64;
+                    [start_pos[0] + dx * t, start_pos[1] + dy * t, heading]
+                })
+                .collect();
 
-                    let task_id = req
+            json!({
+                "points": points,
+                "total_distance": (dx * dx + dy * dy).sqrt(),
+                "ret_code": 0,
+                "err_msg": "",
+                "create_on": get_timestamp()
+            })
+            .to_string()
+        }
+        3066 => {
+            // MoveToTargetList
+            let mut s = state.write().await;
+            let wp = waypoints.read().await;
+
+            if let Ok(req) =
+                serde_json::from_str::<serde_json::Value>(&frame.body)
+                && let Some(task_list) =
+                    req.get("move_task_list").and_then(|v| v.as_array())
+            {
+                // Clear old task queue only when starting new navigation
+                s.task_queue.clear();
+                s.current_task_index = 0;
+
+                // Parse each task in the list
+                for (idx, task) in task_list.iter().enumerate() {
+                    let target =
+                        task.get("id").and_then(|v| v.as_str()).unwrap_or("");
+                    let start = task
+                        .get("source_id")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("SELF_POSITION");
+                    let task_id = task
                         .get("task_id")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string())
-                        .unwrap_or_else(|| "single_task".to_string());
+                        .unwrap_or_else(|| format!("task_{}", idx));
 
                     // Get positions from waypoints
                     let start_pos = if start == "SELF_POSITION" {
@@ -704,98 +1426,77 @@ async fn handle_request(
                             0.0,
                         ]);
 
-                    // Create single task
                     s.task_queue.push(NavTask {
                         task_id,
                         start: start.to_string(),
                         target: target.to_string(),
                         start_pos,
                         target_pos,
-                        status: 2, // Running
+                        status: if idx == 0 { 2 } else { 1 }, // First task running, others waiting
                     });
+                }
 
+                if !s.task_queue.is_empty() {
                     s.nav_status = 2; // Running
                     s.nav_type = 3; // Path nav
-                    s.target_id = target.to_string();
-                    s.target_point = target_pos;
+                    s.target_id = s.task_queue[0].target.clone();
+                    s.target_point = s.task_queue[0].target_pos;
                 }
             }
 
             json!({
                 "ret_code": 0,
-                "err_msg": "Navigation started",
+                "err_msg": "Path navigation started",
                 "create_on": get_timestamp()
             })
             .to_string()
         }
-        3066 => {
-            // MoveToTargetList
-            let mut s = state.write().await;
-            let wp = waypoints.read().await;
 
-            if let Ok(req) =
+        3068 => {
+            // SafeClearMovements: cancel a single task of the queue
+            let mut s = state.write().await;
+            let task_id =
                 serde_json::from_str::<serde_json::Value>(&frame.body)
-            {
-                if let Some(task_list) =
-                    req.get("move_task_list").and_then(|v| v.as_array())
-                {
-                    // Clear old task queue only when starting new navigation
-                    s.task_queue.clear();
-                    s.current_task_index = 0;
-
-                    // Parse each task in the list
-                    for (idx, task) in task_list.iter().enumerate() {
-                        let target = task
-                            .get("id")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("");
-                        let start = task
-                            .get("source_id")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("SELF_POSITION");
-                        let task_id = task
-                            .get("task_id")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| format!("task_{}", idx));
-
-                        // Get positions from waypoints
-                        let start_pos = if start == "SELF_POSITION" {
-                            [s.x, s.y, s.angle]
-                        } else {
-                            wp.get(start)
-                                .map(|w| [w.x, w.y, 0.0])
-                                .unwrap_or([s.x, s.y, s.angle])
-                        };
-
-                        let target_pos =
-                            wp.get(target).map(|w| [w.x, w.y, 0.0]).unwrap_or(
-                                [start_pos[0] + 5.0, start_pos[1] + 5.0, 0.0],
-                            );
-
-                        s.task_queue.push(NavTask {
-                            task_id,
-                            start: start.to_string(),
-                            target: target.to_string(),
-                            start_pos,
-                            target_pos,
-                            status: if idx == 0 { 2 } else { 1 }, // First task running, others waiting
-                        });
-                    }
-
-                    if !s.task_queue.is_empty() {
-                        s.nav_status = 2; // Running
-                        s.nav_type = 3; // Path nav
-                        s.target_id = s.task_queue[0].target.clone();
-                        s.target_point = s.task_queue[0].target_pos;
+                    .ok()
+                    .and_then(|req| {
+                        req["task_id"].as_str().map(str::to_string)
+                    });
+            let index = task_id.and_then(|id| {
+                s.task_queue.iter().position(|task| task.task_id == id)
+            });
+
+            match index {
+                Some(index) if matches!(s.task_queue[index].status, 1 | 2) => {
+                    s.task_queue[index].status = 6; // Canceled
+                    if index == s.current_task_index && s.nav_status == 2 {
+                        start_next_task(&mut s);
                     }
+                    json!({
+                        "ret_code": 0,
+                        "err_msg": "Task cleared",
+                        "create_on": get_timestamp()
+                    })
+                    .to_string()
                 }
+                Some(_) => json!({
+                    "ret_code": 40001,
+                    "err_msg": "Task already finished"
+                })
+                .to_string(),
+                None => json!({
+                    "ret_code": 40001,
+                    "err_msg": "No such task"
+                })
+                .to_string(),
             }
+        }
 
+        3115 => {
+            // Task chain list
             json!({
+                "tasklists": ["pick_and_place", "charging_loop"],
                 "ret_code": 0,
-                "err_msg": "Path navigation started",
-                "create_on": get_timestamp()
+                "err_msg": ""
             })
             .to_string()
         }
@@ -803,6 +1504,17 @@ async fn handle_request(
         // Config APIs (4000-5999)
         4005 => {
             // Lock control
+            let mut s = state.write().await;
+            let nick_name =
+                serde_json::from_str::<serde_json::Value>(&frame.body)
+                    .ok()
+                    .and_then(|req| {
+                        req.get("nick_name")
+                            .and_then(|v| v.as_str())
+                            .map(String::from)
+                    })
+                    .unwrap_or_default();
+            s.lock_owner = Some((nick_name, peer_ip.to_string()));
             json!({
                 "ret_code": 0,
                 "err_msg": "Control locked"
@@ -811,6 +1523,8 @@ async fn handle_request(
         }
         4006 => {
             // Unlock control
+            let mut s = state.write().await;
+            s.lock_owner = None;
             json!({
                 "ret_code": 0,
                 "err_msg": "Control unlocked"
@@ -825,18 +1539,202 @@ async fn handle_request(
             })
             .to_string()
         }
-        4100 => {
-            // Set params
+        4100 | 4101 => {
+            // Set params, saving them to the config for 4101
+            let mut s = state.write().await;
+            let body = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            merge_params(&mut s.params, &body);
+            if frame.api_no == 4101 {
+                merge_params(&mut s.saved_params, &body);
+            }
             json!({
                 "ret_code": 0,
                 "err_msg": "Parameters set"
             })
             .to_string()
         }
+        4102 => {
+            // Reload params from the config
+            let mut s = state.write().await;
+            s.params = s.saved_params.clone();
+            json!({
+                "ret_code": 0,
+                "err_msg": "Parameters reloaded"
+            })
+            .to_string()
+        }
+
+        4021 => {
+            // Upload script
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            match (
+                req.get("name").and_then(|v| v.as_str()),
+                req.get("content").and_then(|v| v.as_str()),
+            ) {
+                (Some(name), Some(content)) => {
+                    s.scripts.insert(name.to_string(), content.to_string());
+                    json!({
+                        "ret_code": 0,
+                        "err_msg": ""
+                    })
+                }
+                _ => json!({
+                    "ret_code": 1,
+                    "err_msg": "Missing script name or content"
+                }),
+            }
+            .to_string()
+        }
+        4022 | 4023 => {
+            // Download / remove script
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let name = req.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let script = if api_no == 4022 {
+                s.scripts.get(name).cloned()
+            } else {
+                s.scripts.remove(name)
+            };
+            match script {
+                Some(content) if api_no == 4022 => json!({
+                    "content": content,
+                    "ret_code": 0,
+                    "err_msg": ""
+                }),
+                Some(_) => json!({
+                    "ret_code": 0,
+                    "err_msg": ""
+                }),
+                None => json!({
+                    "ret_code": 1,
+                    "err_msg": format!("No such script: {}", name)
+                }),
+            }
+            .to_string()
+        }
+        4091 => {
+            // Configure push port
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            s.push_apis = req
+                .get("api_list")
+                .and_then(|v| v.as_array())
+                .map(|apis| {
+                    apis.iter()
+                        .filter_map(|v| v.as_u64())
+                        .map(|v| v as u16)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Some(interval) = req.get("interval").and_then(|v| v.as_u64())
+            {
+                s.push_interval_ms = interval as u32;
+            }
+            println!(
+                "Push configured: APIs {:?} every {} ms",
+                s.push_apis, s.push_interval_ms
+            );
+            json!({
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        4450 => {
+            // Clear odometry
+            let mut s = state.write().await;
+            s.mileage = 0.0;
+            s.session_time = 0.0;
+            s.total_time = 0.0;
+            json!({
+                "ret_code": 0,
+                "err_msg": "Odometry cleared"
+            })
+            .to_string()
+        }
+        4353 => {
+            // 3D QR tag mapping
+            json!({
+                "ret_code": 0,
+                "err_msg": "Tag mapping started"
+            })
+            .to_string()
+        }
+        4800 | 4802 => {
+            // Set third-party error / warning
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let code = req.get("code").and_then(|v| v.as_u64()).unwrap_or(0);
+            let alarm = json!({
+                "code": code,
+                "desc": req.get("msg").and_then(|v| v.as_str()).unwrap_or(""),
+                "level": req.get("level"),
+                "timestamp": get_timestamp()
+            });
+            let alarms = if api_no == 4800 {
+                &mut s.errors
+            } else {
+                &mut s.warnings
+            };
+            alarms.retain(|a| a["code"].as_u64() != Some(code));
+            alarms.push(alarm);
+            json!({
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        4801 | 4803 => {
+            // Clear third-party error / warning
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let code = req.get("code").and_then(|v| v.as_u64());
+            let alarms = if api_no == 4801 {
+                &mut s.errors
+            } else {
+                &mut s.warnings
+            };
+            alarms.retain(|a| a["code"].as_u64() != code);
+            json!({
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        4460 => {
+            // Reset GNSS configuration
+            json!({
+                "ret_code": 0,
+                "err_msg": "GNSS configuration reset"
+            })
+            .to_string()
+        }
+        4461 => {
+            // Set GNSS baudrate
+            json!({
+                "ret_code": 0,
+                "err_msg": "GNSS baudrate set"
+            })
+            .to_string()
+        }
 
         // Peripheral APIs (6000-6998)
         6000 => {
             // Play audio
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            s.playing_audio = req
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(|name| name.to_string());
             json!({
                 "ret_code": 0,
                 "err_msg": "Audio playing"
@@ -851,11 +1749,35 @@ async fn handle_request(
             })
             .to_string()
         }
+        6005 => {
+            // Set charging relay
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            s.charging =
+                req.get("status").and_then(|v| v.as_bool()).unwrap_or(false);
+            json!({
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        6012 => {
+            // Stop audio
+            let mut s = state.write().await;
+            s.playing_audio = None;
+            json!({
+                "ret_code": 0,
+                "err_msg": "Audio stopped"
+            })
+            .to_string()
+        }
         6070 => {
             // Jack load
             let mut s = state.write().await;
             s.jack_has_payload = true;
             s.jack_height = 0.2;
+            s.jack_motion = Some((true, Instant::now()));
             json!({
                 "ret_code": 0,
                 "err_msg": "Jack loading"
@@ -867,14 +1789,42 @@ async fn handle_request(
             let mut s = state.write().await;
             s.jack_has_payload = false;
             s.jack_height = 0.0;
+            s.jack_motion = Some((false, Instant::now()));
             json!({
                 "ret_code": 0,
                 "err_msg": "Jack unloading"
             })
             .to_string()
         }
+        6004 => {
+            // Soft estop
+            let mut s = state.write().await;
+            match serde_json::from_str::<serde_json::Value>(&frame.body)
+                .ok()
+                .and_then(|req| req["status"].as_bool())
+            {
+                Some(status) => {
+                    s.soft_emc = status;
+                    json!({
+                        "ret_code": 0,
+                        "err_msg": if status {
+                            "Soft estop engaged"
+                        } else {
+                            "Soft estop released"
+                        }
+                    })
+                    .to_string()
+                }
+                None => json!({
+                    "ret_code": 40001,
+                    "err_msg": "Missing status"
+                })
+                .to_string(),
+            }
+        }
         6072 => {
             // Jack stop
+            state.write().await.jack_motion = None;
             json!({
                 "ret_code": 0,
                 "err_msg": "Jack stopped"
@@ -886,11 +1836,9 @@ async fn handle_request(
             let mut s = state.write().await;
             if let Ok(req) =
                 serde_json::from_str::<serde_json::Value>(&frame.body)
+                && let Some(height) = req.get("height").and_then(|v| v.as_f64())
             {
-                if let Some(height) = req.get("height").and_then(|v| v.as_f64())
-                {
-                    s.jack_height = height;
-                }
+                s.jack_height = height;
             }
             json!({
                 "ret_code": 0,
@@ -899,6 +1847,200 @@ async fn handle_request(
             .to_string()
         }
 
+        6055 => {
+            // Roller front load
+            json!({
+                "ret_code": 0,
+                "err_msg": "Roller front loading"
+            })
+            .to_string()
+        }
+        6056 => {
+            // Roller front unload
+            json!({
+                "ret_code": 0,
+                "err_msg": "Roller front unloading"
+            })
+            .to_string()
+        }
+        6067 => {
+            // Roller stop
+            json!({
+                "ret_code": 0,
+                "err_msg": "Roller stopped"
+            })
+            .to_string()
+        }
+        6110 => {
+            // Start calibration
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let calib_type =
+                req.get("calib_type").and_then(|v| v.as_u64()).unwrap_or(0);
+            s.calibration = Some((calib_type as u32, Instant::now()));
+            s.last_calibration = None;
+            json!({
+                "ret_code": 0,
+                "err_msg": "Calibration started"
+            })
+            .to_string()
+        }
+        6111 => {
+            // Cancel calibration
+            let mut s = state.write().await;
+            s.calibration = None;
+            json!({
+                "ret_code": 0,
+                "err_msg": "Calibration cancelled"
+            })
+            .to_string()
+        }
+        6112 => {
+            // Calibration result
+            let s = state.read().await;
+            match s.last_calibration {
+                Some(calib_type) => json!({
+                    "success": true,
+                    "data": {
+                        "calib_type": calib_type,
+                        "offset": [0.012, -0.004, 0.0021]
+                    },
+                    "ret_code": 0,
+                    "err_msg": ""
+                }),
+                None => json!({
+                    "success": false,
+                    "data": null,
+                    "ret_code": 0,
+                    "err_msg": "No finished calibration"
+                }),
+            }
+            .to_string()
+        }
+        6801 => {
+            // Unbind goods from their container
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let goods = req.get("goods_id").and_then(|v| v.as_str());
+            let before = s.container_goods.len();
+            s.container_goods.retain(|_, g| Some(g.as_str()) != goods);
+            if s.container_goods.len() == before {
+                json!({
+                    "ret_code": 1,
+                    "err_msg": "Goods not bound to any container"
+                })
+            } else {
+                json!({
+                    "ret_code": 0,
+                    "err_msg": ""
+                })
+            }
+            .to_string()
+        }
+        6802 => {
+            // Unbind goods from a container
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let container = req
+                .get("container_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            if s.container_goods.remove(container).is_some() {
+                json!({
+                    "ret_code": 0,
+                    "err_msg": ""
+                })
+            } else {
+                json!({
+                    "ret_code": 1,
+                    "err_msg": "Container holds no goods"
+                })
+            }
+            .to_string()
+        }
+        6803 => {
+            // Unbind goods from all containers
+            let mut s = state.write().await;
+            s.container_goods.clear();
+            json!({
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        6804 => {
+            // Bind goods to a container
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            match (
+                req.get("container_name").and_then(|v| v.as_str()),
+                req.get("goods_id").and_then(|v| v.as_str()),
+            ) {
+                (Some(container), Some(goods)) => {
+                    s.container_goods
+                        .insert(container.to_string(), goods.to_string());
+                    json!({
+                        "ret_code": 0,
+                        "err_msg": ""
+                    })
+                }
+                _ => json!({
+                    "ret_code": 1,
+                    "err_msg": "Missing container_name or goods_id"
+                }),
+            }
+            .to_string()
+        }
+        6901 => {
+            // Bin detection, a sample result slightly off the expected pose
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let bin_id = req
+                .get("bin_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("bin_01");
+            json!({
+                "detected": bin_id != "missing_bin",
+                "offset_x": 0.012,
+                "offset_y": -0.008,
+                "offset_angle": 0.015,
+                "ret_code": 0,
+                "err_msg": ""
+            })
+            .to_string()
+        }
+        6910 => {
+            // Replay
+            json!({
+                "ret_code": 0,
+                "err_msg": "Replay started"
+            })
+            .to_string()
+        }
+        6086 => {
+            // Write Modbus registers
+            let mut s = state.write().await;
+            let req = serde_json::from_str::<serde_json::Value>(&frame.body)
+                .unwrap_or_default();
+            let addr = req.get("addr").and_then(|v| v.as_u64()).unwrap_or(0);
+            if let Some(values) = req.get("values").and_then(|v| v.as_array()) {
+                for (offset, value) in values.iter().enumerate() {
+                    let value = value.as_u64().unwrap_or(0) as u16;
+                    s.modbus_registers
+                        .insert(addr as u32 + offset as u32, value);
+                }
+            }
+            json!({
+                "ret_code": 0,
+                "err_msg": "Modbus registers written"
+            })
+            .to_string()
+        }
+
         // Kernel APIs (5000, 5003, 5005 per KernelApi enum)
         5000 => {
             // Shutdown
@@ -928,6 +2070,52 @@ async fn handle_request(
     }
 }
 
+/// Handle APIs which carry opaque bytes instead of JSON
+async fn handle_raw_request(
+    state: &Arc<RwLock<RobotState>>,
+    frame: &RbkFrame,
+) -> Option<Vec<u8>> {
+    match frame.api_no {
+        1900 => {
+            // Query transparent data
+            let s = state.read().await;
+            Some(s.transparent_data.clone())
+        }
+        1500 => {
+            // Download model file
+            let s = state.read().await;
+            Some(s.model_file.clone())
+        }
+        4200 => {
+            // Upload model file
+            let mut s = state.write().await;
+            s.model_file = frame.raw_body.clone();
+            Some(
+                json!({
+                    "ret_code": 0,
+                    "err_msg": "Model file updated"
+                })
+                .to_string()
+                .into_bytes(),
+            )
+        }
+        6900 => {
+            // Update transparent data
+            let mut s = state.write().await;
+            s.transparent_data = frame.raw_body.clone();
+            Some(
+                json!({
+                    "ret_code": 0,
+                    "err_msg": "Transparent data updated"
+                })
+                .to_string()
+                .into_bytes(),
+            )
+        }
+        _ => None,
+    }
+}
+
 /// Handle a single client connection
 async fn handle_client(
     mut stream: TcpStream,
@@ -937,6 +2125,11 @@ async fn handle_client(
 ) {
     println!("New connection on port {}", port);
 
+    let peer_ip = stream
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default();
+
     let mut decoder = RbkDecoder::new();
     let mut buf = BytesMut::with_capacity(4096);
     let mut read_buf = vec![0u8; 4096];
@@ -958,18 +2151,33 @@ async fn handle_client(
 
                     let api_no = frame.api_no;
                     let flow_no = frame.flow_no;
+                    state.write().await.received_apis.push(api_no);
                     let response_body =
-                        handle_request(state.clone(), waypoints.clone(), frame)
-                            .await;
-                    let response_bytes =
-                        encode_response(api_no, &response_body, flow_no);
-
-                    if let Err(e) = stream.write_all(&response_bytes).await {
-                        eprintln!("Failed to write response: {}", e);
-                        break;
-                    }
+                        match handle_raw_request(&state, &frame).await {
+                            Some(raw) => raw,
+                            None => handle_request(
+                                state.clone(),
+                                waypoints.clone(),
+                                frame,
+                                &peer_ip,
+                            )
+                            .await
+                            .into_bytes(),
+                        };
+                    // Like the real robot, answer with the request's API
+                    // number offset by 10000
+                    let response_bytes = encode_response(
+                        api_no.wrapping_add(10000),
+                        &response_body,
+                        flow_no,
+                    );
 
-                    stream.flush().await.unwrap();
+                    if let Err(e) =
+                        write_response(&mut stream, &response_bytes).await
+                    {
+                        report_write_error(port, api_no, &e);
+                        return;
+                    }
                 }
             }
             Err(e) => {
@@ -980,6 +2188,35 @@ async fn handle_client(
     }
 }
 
+/// Write a response and flush it out to the client
+async fn write_response(
+    stream: &mut TcpStream,
+    bytes: &[u8],
+) -> std::io::Result<()> {
+    stream.write_all(bytes).await?;
+    stream.flush().await
+}
+
+/// Log a failed response write, a client hanging up mid-write (e.g. a
+/// test tearing down its connection) is expected and not an error
+fn report_write_error(port: u16, api_no: u16, e: &std::io::Error) {
+    use std::io::ErrorKind;
+
+    match e.kind() {
+        ErrorKind::BrokenPipe
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionAborted
+        | ErrorKind::UnexpectedEof => println!(
+            "Client on port {} disconnected before response to API {}",
+            port, api_no
+        ),
+        _ => eprintln!(
+            "Failed to write response to API {} on port {}: {}",
+            api_no, port, e
+        ),
+    }
+}
+
 /// Start a server on a specific port
 async fn start_server(
     port: u16,
@@ -1026,8 +2263,10 @@ async fn simulate_robot_behavior(state: Arc<RwLock<RobotState>>) {
 
         let mut s = state.write().await;
 
-        // Simulate battery drain
-        if !s.charging && s.battery_level > 0.1 {
+        // Simulate battery drain, or charging while the relay is engaged
+        if s.charging {
+            s.battery_level = (s.battery_level + 0.005).min(1.0);
+        } else if s.battery_level > 0.1 {
             s.battery_level -= 0.00005;
         }
 
@@ -1066,33 +2305,71 @@ async fn simulate_robot_behavior(state: Arc<RwLock<RobotState>>) {
                 s.angle = target_angle;
                 s.task_queue[current_idx].status = 4; // Completed
 
-                // Move to next task
-                s.current_task_index += 1;
-                let next_idx = s.current_task_index;
-
-                if next_idx < s.task_queue.len() {
-                    // Start next task
-                    s.task_queue[next_idx].status = 2; // Running
-                    s.target_id = s.task_queue[next_idx].target.clone();
-                    s.target_point = s.task_queue[next_idx].target_pos;
-                    println!(
-                        "Moving to next task: {} -> {}",
-                        s.task_queue[next_idx].start,
-                        s.task_queue[next_idx].target
-                    );
-                } else {
-                    // All tasks completed
-                    s.nav_status = 4; // Completed
-                    println!("All navigation tasks completed!");
-                }
+                start_next_task(&mut s);
             }
         }
 
-        // Update total time
+        // Update uptimes
+        s.session_time += 500.0;
         s.total_time += 500.0;
     }
 }
 
+/// Params of a freshly booted robot
+fn default_params() -> serde_json::Value {
+    json!({
+        "MoveFactory": {
+            "maxSpeed": 1.0,
+            "maxRot": 0.8
+        }
+    })
+}
+
+/// Overwrite the params in `params` with those given in `update`, both
+/// keyed by plugin and then param name
+fn merge_params(params: &mut serde_json::Value, update: &serde_json::Value) {
+    let Some(update) = update.as_object() else {
+        return;
+    };
+    for (plugin, values) in update {
+        let Some(values) = values.as_object() else {
+            continue;
+        };
+        for (name, value) in values {
+            params[plugin][name] = value.clone();
+        }
+    }
+}
+
+/// Move on to the next task of the queue which was not canceled, or
+/// complete the navigation if there is none
+fn start_next_task(s: &mut RobotState) {
+    s.current_task_index += 1;
+    while s
+        .task_queue
+        .get(s.current_task_index)
+        .is_some_and(|task| task.status == 6)
+    {
+        s.current_task_index += 1;
+    }
+
+    let next_idx = s.current_task_index;
+    if next_idx < s.task_queue.len() {
+        // Start next task
+        s.task_queue[next_idx].status = 2; // Running
+        s.target_id = s.task_queue[next_idx].target.clone();
+        s.target_point = s.task_queue[next_idx].target_pos;
+        println!(
+            "Moving to next task: {} -> {}",
+            s.task_queue[next_idx].start, s.task_queue[next_idx].target
+        );
+    } else {
+        // All tasks completed
+        s.nav_status = 4; // Completed
+        println!("All navigation tasks completed!");
+    }
+}
+
 // HTTP API Handlers
 
 /// POST /waypoints - Add waypoints
@@ -1129,6 +2406,13 @@ async fn delete_waypoint(
     }
 }
 
+/// GET /requests - API numbers of all requests received so far
+async fn get_requests(
+    AxumState(state): AxumState<Arc<AppState>>,
+) -> Json<Vec<u16>> {
+    Json(state.robot.read().await.received_apis.clone())
+}
+
 /// Start HTTP server for waypoint management
 async fn start_http_server(state: Arc<AppState>) {
     let cors = CorsLayer::new()
@@ -1140,6 +2424,7 @@ async fn start_http_server(state: Arc<AppState>) {
         .route("/waypoints", post(add_waypoints))
         .route("/waypoints", get(get_waypoints))
         .route("/waypoints/:id", delete(delete_waypoint))
+        .route("/requests", get(get_requests))
         .layer(cors)
         .with_state(state);
 
@@ -1192,8 +2477,8 @@ async fn main() {
     }
 
     let app_state = Arc::new(AppState {
-        robot: robot_state.clone(),
         waypoints: waypoints.clone(),
+        robot: robot_state.clone(),
     });
 
     // Start behavior simulation
@@ -1236,6 +2521,7 @@ async fn main() {
     println!("    - POST   /waypoints");
     println!("    - GET    /waypoints");
     println!("    - DELETE /waypoints/{{id}}");
+    println!("    - GET    /requests");
     println!("  Press Ctrl+C to stop\n");
 
     // Wait for all servers
@@ -54,7 +54,6 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
-use reqwest;
 use seersdk_rs::*;
 use serde::{Deserialize, Serialize};
 use std::io;
@@ -101,7 +100,7 @@ impl App {
             "Press '?' in Normal mode for help...".to_string(),
         ];
         let mut scroll_state = ListState::default();
-        let scroll_offset = if messages.len() > 0 {
+        let scroll_offset = if !messages.is_empty() {
             messages.len() - 1
         } else {
             0
@@ -267,7 +266,7 @@ impl App {
     }
 
     async fn execute_command(&mut self, cmd: &str) {
-        let parts: Vec<&str> = cmd.trim().split_whitespace().collect();
+        let parts: Vec<&str> = cmd.split_whitespace().collect();
         if parts.is_empty() {
             return;
         }
@@ -530,6 +529,14 @@ impl App {
                 self.add_message(format!("  Status: {:?}", response.status));
                 self.add_message(format!("  Type: {:?}", response.ty));
                 self.add_message(format!("  Target: {}", response.target_id));
+                if response.is_path_nav() {
+                    let (done, left) = response.progress();
+                    self.add_message(format!(
+                        "  Progress: {}/{} stations",
+                        done,
+                        done + left
+                    ));
+                }
                 Ok(())
             }
             Err(e) => Err(format!("Failed to query nav status: {}", e)),
@@ -727,106 +734,101 @@ async fn run_app<B: ratatui::backend::Backend>(
         }
 
         // Poll for events with timeout
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+        {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
 
-                match app.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => {
-                            app.should_quit = true;
-                        }
-                        KeyCode::Char('i') => {
-                            app.input_mode = InputMode::Editing;
-                        }
-                        KeyCode::Char('?') => {
-                            app.show_help();
-                        }
-                        KeyCode::Char('c') => {
-                            app.clear_screen();
-                        }
-                        KeyCode::Char('k') | KeyCode::Up => {
-                            app.scroll_up();
-                        }
-                        KeyCode::Char('j') | KeyCode::Down => {
-                            app.scroll_down();
-                        }
-                        KeyCode::Char('u') | KeyCode::PageUp => {
-                            app.scroll_page_up(10);
-                        }
-                        KeyCode::Char('d') | KeyCode::PageDown => {
-                            app.scroll_page_down(10);
-                        }
-                        KeyCode::Char('g') | KeyCode::Home => {
-                            app.scroll_to_top();
-                        }
-                        KeyCode::Char('G') | KeyCode::End => {
-                            app.scroll_to_bottom();
+            match app.input_mode {
+                InputMode::Normal => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Char('i') => {
+                        app.input_mode = InputMode::Editing;
+                    }
+                    KeyCode::Char('?') => {
+                        app.show_help();
+                    }
+                    KeyCode::Char('c') => {
+                        app.clear_screen();
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        app.scroll_up();
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        app.scroll_down();
+                    }
+                    KeyCode::Char('u') | KeyCode::PageUp => {
+                        app.scroll_page_up(10);
+                    }
+                    KeyCode::Char('d') | KeyCode::PageDown => {
+                        app.scroll_page_down(10);
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => {
+                        app.scroll_to_top();
+                    }
+                    KeyCode::Char('G') | KeyCode::End => {
+                        app.scroll_to_bottom();
+                    }
+                    _ => {}
+                },
+                InputMode::Editing => {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        match key.code {
+                            KeyCode::Char('k') | KeyCode::Up => {
+                                app.scroll_up();
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => {
+                                app.scroll_down();
+                            }
+                            KeyCode::Char('c') => {
+                                app.clear_screen();
+                            }
+                            _ => {}
                         }
-                        _ => {}
-                    },
-                    InputMode::Editing => {
-                        if key.modifiers.contains(KeyModifiers::CONTROL) {
-                            match key.code {
-                                KeyCode::Char('k') | KeyCode::Up => {
-                                    app.scroll_up();
-                                }
-                                KeyCode::Char('j') | KeyCode::Down => {
-                                    app.scroll_down();
-                                }
-                                KeyCode::Char('c') => {
-                                    app.clear_screen();
-                                }
-                                _ => {}
+                    } else {
+                        match key.code {
+                            KeyCode::Enter => {
+                                let cmd =
+                                    app.input.drain(..).collect::<String>();
+                                app.cursor_position = 0;
+                                app.execute_command(&cmd).await;
                             }
-                        } else {
-                            match key.code {
-                                KeyCode::Enter => {
-                                    let cmd =
-                                        app.input.drain(..).collect::<String>();
-                                    app.cursor_position = 0;
-                                    app.execute_command(&cmd).await;
-                                }
-                                KeyCode::Char(c) => {
-                                    app.input.insert(app.cursor_position, c);
-                                    app.cursor_position += 1;
-                                }
-                                KeyCode::Backspace => {
-                                    if app.cursor_position > 0 {
-                                        app.input
-                                            .remove(app.cursor_position - 1);
-                                        app.cursor_position -= 1;
-                                    }
-                                }
-                                KeyCode::Left => {
-                                    if app.cursor_position > 0 {
-                                        app.cursor_position -= 1;
-                                    }
-                                }
-                                KeyCode::Right => {
-                                    if app.cursor_position < app.input.len() {
-                                        app.cursor_position += 1;
-                                    }
-                                }
-                                KeyCode::PageUp => {
-                                    app.scroll_page_up(10);
-                                }
-                                KeyCode::PageDown => {
-                                    app.scroll_page_down(10);
-                                }
-                                KeyCode::Home => {
-                                    app.scroll_to_top();
-                                }
-                                KeyCode::End => {
-                                    app.scroll_to_bottom();
-                                }
-                                KeyCode::Esc => {
-                                    app.input_mode = InputMode::Normal;
-                                }
-                                _ => {}
+                            KeyCode::Char(c) => {
+                                app.input.insert(app.cursor_position, c);
+                                app.cursor_position += 1;
+                            }
+                            KeyCode::Backspace if app.cursor_position > 0 => {
+                                app.input.remove(app.cursor_position - 1);
+                                app.cursor_position -= 1;
+                            }
+                            KeyCode::Left if app.cursor_position > 0 => {
+                                app.cursor_position -= 1;
+                            }
+                            KeyCode::Right
+                                if app.cursor_position < app.input.len() =>
+                            {
+                                app.cursor_position += 1;
+                            }
+                            KeyCode::PageUp => {
+                                app.scroll_page_up(10);
+                            }
+                            KeyCode::PageDown => {
+                                app.scroll_page_down(10);
+                            }
+                            KeyCode::Home => {
+                                app.scroll_to_top();
+                            }
+                            KeyCode::End => {
+                                app.scroll_to_bottom();
+                            }
+                            KeyCode::Esc => {
+                                app.input_mode = InputMode::Normal;
                             }
+                            _ => {}
                         }
                     }
                 }
@@ -18,10 +18,13 @@ pub type PointId = String;
 /// - State APIs (1000-1999): Robot state queries on port 19204
 /// - Control APIs (2000-2999): Robot control commands on port 19205
 /// - Navigation APIs (3000-3999): Navigation commands on port 19206
-/// - Config APIs (4000-5999): Configuration management on port 19207
-/// - Kernel APIs (7000-7999): Kernel operations on port 19208
-/// - Misc APIs (6000-6998): Miscellaneous operations on port 19210
-/// - Push APIs (9000+): Push configuration and push data
+/// - Config APIs (4000-4999): Configuration management on port 19207
+/// - Kernel APIs (5000-5999): Kernel operations on port 19208
+/// - Misc APIs (6000-6998): Peripheral operations on port 19210
+/// - Push APIs (9000+): Push configuration and push data on port 19210
+///
+/// APIs the SDK does not know of, such as firmware-specific extensions,
+/// are given by number as [`ApiRequest::Custom`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u16)]
 pub enum ApiRequest {
@@ -31,14 +34,17 @@ pub enum ApiRequest {
     Control(ControlApi),
     /// Navigation module APIs (3000-3999)
     Nav(NavApi),
-    /// Config module APIs (4000-5999)
+    /// Config module APIs (4000-4999)
     Config(ConfigApi),
-    /// Kernel module APIs (7000-7999)
+    /// Kernel module APIs (5000-5999)
     Kernel(KernelApi),
-    /// Misc module APIs (6000-6998)
+    /// Misc module APIs (6000-6998), peripheral operations
     Peripheral(PeripheralApi),
     /// Push module APIs (9000+)
     Push(PushApi),
+    /// API given by its number, sent to the port of the module whose range
+    /// contains it
+    Custom(u16),
 }
 
 impl ApiRequest {
@@ -52,6 +58,7 @@ impl ApiRequest {
             ApiRequest::Kernel(api) => api as u16,
             ApiRequest::Peripheral(api) => api as u16,
             ApiRequest::Push(api) => api as u16,
+            ApiRequest::Custom(api_no) => api_no,
         }
     }
 }
@@ -59,6 +66,10 @@ impl ApiRequest {
 /// Macro to generate request DTO types for RBK robot APIs
 ///
 /// This macro creates a request type with associated traits for serialization and response handling.
+/// It is exported so that downstream crates can define typed requests for
+/// APIs the SDK does not wrap, usually with [`ApiRequest::Custom`]. The
+/// generated type implements [`ToRequestBody`] and [`FromResponseBody`] and
+/// can be passed to [`crate::RbkClient::request`] like any built-in request.
 ///
 /// # Patterns
 ///
@@ -72,6 +83,44 @@ impl ApiRequest {
 /// impl_api_request!(RequestTypeName, ApiRequest::Module(ModuleApi::Variant), req: PayloadType, res: ResponseType);
 /// ```
 ///
+/// 3. Request with a payload type already used by another request, no
+///    `into_request()` is generated for it:
+/// ```ignore
+/// impl_api_request!(RequestTypeName, ApiRequest::Module(ModuleApi::Variant), shared req: PayloadType, res: ResponseType);
+/// ```
+///
+/// # Example
+///
+/// A firmware-specific query defined in a downstream crate:
+///
+/// ```no_run
+/// use seersdk_rs::{ApiRequest, RbkClient, StatusCode, impl_api_request};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// pub struct LiftQuery {
+///     pub lift_id: u32,
+/// }
+///
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// pub struct LiftState {
+///     pub height: f64,
+///     #[serde(rename = "ret_code", default)]
+///     pub code: Option<StatusCode>,
+/// }
+///
+/// impl_api_request!(LiftStateRequest, ApiRequest::Custom(1999), req: LiftQuery, res: LiftState);
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = RbkClient::new("192.168.8.114");
+/// let request = LiftQuery { lift_id: 1 }.into_request();
+/// let state = client.request(request, Duration::from_secs(5)).await?;
+///
+/// println!("Lift at {} m", state.height);
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Arguments
 ///
 /// * `$req_type` - Name of the request type to generate
@@ -79,6 +128,7 @@ impl ApiRequest {
 /// * `$req_body_type` - (Optional) Type of the request payload for requests that need a body
 /// * `$res_type` - Type of the response that will be returned
 /// * `$docs` - (Optional) Documentation string for the generated request type
+#[macro_export]
 macro_rules! impl_api_request {
     // Pattern for requests without payload
     ($req_type:ident, $api_variant:expr, res: $res_type:ty $(, $docs:literal)?) => {
@@ -92,22 +142,33 @@ macro_rules! impl_api_request {
             }
         }
 
-        impl $crate::api::ToRequestBody for $req_type {
-            fn to_request_body(&self) -> Result<String, serde_json::Error> {
-                Ok(String::new())
+        impl $crate::ToRequestBody for $req_type {
+            fn to_request_body(&self) -> Result<::std::borrow::Cow<'static, str>, $crate::__private::serde_json::Error> {
+                Ok(::std::borrow::Cow::Borrowed(""))
             }
 
-            fn to_api_request(&self) -> ApiRequest {
+            fn to_api_request(&self) -> $crate::ApiRequest {
                 $api_variant
             }
         }
 
-        impl $crate::api::FromResponseBody for $req_type {
+        impl $crate::FromResponseBody for $req_type {
             type Response = $res_type;
         }
     };
     // Pattern for requests with payload
     ($req_type:ident, $api_variant:expr, req: $req_body_type:ty, res: $res_type:ty $(, $docs:literal)?) => {
+        $crate::impl_api_request!($req_type, $api_variant, shared req: $req_body_type, res: $res_type $(, $docs)?);
+
+        impl $req_body_type {
+            pub fn into_request(self) -> $req_type {
+                $req_type { req_body: self }
+            }
+        }
+    };
+    // Pattern for requests whose payload type is already used by another
+    // request, the payload has no `into_request()` for this one
+    ($req_type:ident, $api_variant:expr, shared req: $req_body_type:ty, res: $res_type:ty $(, $docs:literal)?) => {
         $(#[doc = $docs])?
         #[derive(Debug, Clone)]
         pub struct $req_type {
@@ -120,23 +181,17 @@ macro_rules! impl_api_request {
             }
         }
 
-        impl $req_body_type {
-            pub fn into_request(self) -> $req_type {
-                $req_type { req_body: self }
-            }
-        }
-
-        impl $crate::api::ToRequestBody for $req_type {
-            fn to_request_body(&self) -> Result<String, serde_json::Error> {
-                serde_json::to_string(&self.req_body)
+        impl $crate::ToRequestBody for $req_type {
+            fn to_request_body(&self) -> Result<::std::borrow::Cow<'static, str>, $crate::__private::serde_json::Error> {
+                $crate::__private::serde_json::to_string(&self.req_body).map(::std::borrow::Cow::Owned)
             }
 
-            fn to_api_request(&self) -> ApiRequest {
+            fn to_api_request(&self) -> $crate::ApiRequest {
                 $api_variant
             }
         }
 
-        impl $crate::api::FromResponseBody for $req_type {
+        impl $crate::FromResponseBody for $req_type {
             type Response = $res_type;
         }
     };
@@ -146,32 +201,51 @@ macro_rules! impl_api_request {
 impl_api_request!(CommonInfoRequest, ApiRequest::State(StateApi::Info), res: CommonInfo);
 impl_api_request!(OperationInfoRequest, ApiRequest::State(StateApi::Run), res: OperationInfo);
 impl_api_request!(RobotPoseRequest, ApiRequest::State(StateApi::Loc), res: RobotPose);
-impl_api_request!(RobotSpeedRequest, ApiRequest::State(StateApi::Speed), res: StatusMessage);
+impl_api_request!(RobotSpeedRequest, ApiRequest::State(StateApi::Speed), res: RobotSpeed);
 impl_api_request!(BlockStatusRequest, ApiRequest::State(StateApi::Block), res: BlockStatus);
 impl_api_request!(BatteryStatusRequest, ApiRequest::State(StateApi::Battery), res: BatteryStatus);
-impl_api_request!(RobotLidarDataRequest, ApiRequest::State(StateApi::Laser), res: StatusMessage);
-impl_api_request!(RobotCurrentAreaRequest, ApiRequest::State(StateApi::Area), res: StatusMessage);
+impl_api_request!(RobotEncoderStatusRequest, ApiRequest::State(StateApi::Encoder), res: EncoderStatus);
+impl_api_request!(RobotImuRequest, ApiRequest::State(StateApi::Imu), res: ImuData);
+impl_api_request!(RobotMotorStatusRequest, ApiRequest::State(StateApi::Motor), res: MotorStatus);
+impl_api_request!(RobotLidarDataRequest, ApiRequest::State(StateApi::Laser), res: LidarData);
+impl_api_request!(RobotBinsRequest, ApiRequest::State(StateApi::Bins), res: Bins);
+impl_api_request!(RobotCurrentAreaRequest, ApiRequest::State(StateApi::Area), res: RobotArea);
 impl_api_request!(RobotEmergencyStatusRequest, ApiRequest::State(StateApi::Emergency), res: StatusMessage);
 impl_api_request!(RobotIODataRequest, ApiRequest::State(StateApi::Io), res: StatusMessage);
-impl_api_request!(NavStatusRequest, ApiRequest::State(StateApi::Nav), req: GetNavStatus, res: NavStatus);
-impl_api_request!(TaskStatusRequest, ApiRequest::State(StateApi::TaskPackage), req: GetTaskStatus, res: TaskPackage);
-impl_api_request!(RobotRelocationStatusRequest, ApiRequest::State(StateApi::Reloc), res: StatusMessage);
+impl_api_request!(NavStatusRequest, ApiRequest::State(StateApi::Nav), req: GetNavStatus, res: NavStatus, "Query the current navigation (1020): target, task type and the stations passed so far");
+impl_api_request!(TaskStatusRequest, ApiRequest::State(StateApi::TaskPackage), req: GetTaskStatus, res: TaskPackage, "Query the task status package (1110): the status of every queued task, not the navigation itself");
+impl_api_request!(RobotRelocationStatusRequest, ApiRequest::State(StateApi::Reloc), res: RelocationStatus);
 impl_api_request!(RobotLoadMapStatusRequest, ApiRequest::State(StateApi::LoadMap), res: StatusMessage);
 impl_api_request!(RobotSlamStatusRequest, ApiRequest::State(StateApi::Slam), res: StatusMessage);
-impl_api_request!(JackStatusRequest, ApiRequest::State(StateApi::Jack), res: StatusMessage);
+impl_api_request!(JackStatusRequest, ApiRequest::State(StateApi::Jack), res: JackStatus);
+impl_api_request!(ForkStatusRequest, ApiRequest::State(StateApi::Fork), res: ForkStatus);
 impl_api_request!(RobotAlarmStatusRequest, ApiRequest::State(StateApi::Alarm), res: StatusMessage);
-impl_api_request!(RobotAllStatus1Request, ApiRequest::State(StateApi::All1), res: StatusMessage);
-impl_api_request!(RobotAllStatus2Request, ApiRequest::State(StateApi::All2), res: StatusMessage);
-impl_api_request!(RobotAllStatus3Request, ApiRequest::State(StateApi::All3), res: StatusMessage);
+impl_api_request!(CurrentLockRequest, ApiRequest::State(StateApi::CurrentLock), res: CurrentLock);
+impl_api_request!(ReadModbusRequest, ApiRequest::State(StateApi::Modbus), req: ReadModbus, res: ModbusData);
+impl_api_request!(GnssStatusRequest, ApiRequest::State(StateApi::GnssCheck), res: GnssStatus);
+impl_api_request!(GnssListRequest, ApiRequest::State(StateApi::GnssList), res: GnssList);
+impl_api_request!(ScriptListRequest, ApiRequest::State(StateApi::ScriptInfo), res: ScriptList);
+impl_api_request!(RobotSoundStatusRequest, ApiRequest::State(StateApi::Sound), res: SoundStatus);
+impl_api_request!(Tag3dRequest, ApiRequest::State(StateApi::Tag3D), res: Tag3dData);
+impl_api_request!(ArmStatusRequest, ApiRequest::State(StateApi::ArmStatus), res: ArmStatus);
+impl_api_request!(ArmMoveRequest, ApiRequest::State(StateApi::ArmMove), req: ArmMove, res: StatusMessage);
+impl_api_request!(CalibrationStatusRequest, ApiRequest::State(StateApi::CalibStatus), res: CalibrationStatus);
+impl_api_request!(RobotAllStatus1Request, ApiRequest::State(StateApi::All1), res: AllStatus1, "Query pose, battery and block status in one request");
+impl_api_request!(RobotAllStatus2Request, ApiRequest::State(StateApi::All2), res: AllStatus2, "Query speed, navigation and area status in one request");
+impl_api_request!(RobotAllStatus3Request, ApiRequest::State(StateApi::All3), res: AllStatus3, "Query jack, fork and digital I/O status in one request");
 impl_api_request!(RobotMapInfoRequest, ApiRequest::State(StateApi::Map), res: StatusMessage);
 impl_api_request!(RobotParamsRequest, ApiRequest::State(StateApi::Params), res: StatusMessage);
+impl_api_request!(ListFilesRequest, ApiRequest::State(StateApi::ListFile), res: FileList, "Query the files stored on the robot");
 
 // Control API requests
 impl_api_request!(StopExerciseRequest, ApiRequest::Control(ControlApi::Stop), res: StatusMessage);
 impl_api_request!(RelocateRequest, ApiRequest::Control(ControlApi::Reloc), res: StatusMessage);
 impl_api_request!(ConfirmLocationRequest, ApiRequest::Control(ControlApi::ComfirmLoc), res: StatusMessage);
+impl_api_request!(CancelRelocationRequest, ApiRequest::Control(ControlApi::CancelReloc), res: StatusMessage, "Abort a relocation in progress, check the outcome with RobotRelocationStatusRequest");
 impl_api_request!(OpenLoopMotionRequest, ApiRequest::Control(ControlApi::Motion), res: StatusMessage);
 impl_api_request!(SwitchMapRequest, ApiRequest::Control(ControlApi::LoadMap), res: StatusMessage);
+impl_api_request!(ClearMotorEncoderRequest, ApiRequest::Control(ControlApi::ClearMotorEncoder), res: StatusMessage);
+impl_api_request!(ClearWeightRequest, ApiRequest::Control(ControlApi::ClearWeightdevvalue), res: StatusMessage, "Zero (tare) the weight sensor");
 
 // Navigation API requests
 impl_api_request!(PauseTaskRequest, ApiRequest::Nav(NavApi::Pause), res: StatusMessage);
@@ -180,13 +254,276 @@ impl_api_request!(CancelTaskRequest, ApiRequest::Nav(NavApi::Cancel), res: Statu
 impl_api_request!(MoveToTargetRequest, ApiRequest::Nav(NavApi::MoveToTarget), req: MoveToTarget, res: StatusMessage);
 impl_api_request!(TranslateRequest, ApiRequest::Nav(NavApi::Translate), res: StatusMessage);
 impl_api_request!(TurnRequest, ApiRequest::Nav(NavApi::Turn), res: StatusMessage);
+impl_api_request!(TargetPathRequest, ApiRequest::Nav(NavApi::TargetPath), req: GetTargetPath, res: NavPath);
 impl_api_request!(MoveDesignedPathRequest, ApiRequest::Nav(NavApi::MoveToTargetList), req: MoveDesignedPath, res: StatusMessage);
+impl_api_request!(SafeClearMovementsRequest, ApiRequest::Nav(NavApi::SafeClearMovements), req: SafeClearMovements, res: StatusMessage, "Cancel a single task of a designed path by its task id");
+impl_api_request!(TaskListNamesRequest, ApiRequest::Nav(NavApi::TaskListList), res: TaskListNames);
+
+// Config API requests
+impl_api_request!(SetParamsRequest, ApiRequest::Config(ConfigApi::SetParams), req: SetParams, res: StatusMessage, "Set robot params until the next reload or reboot");
+impl_api_request!(SaveParamsRequest, ApiRequest::Config(ConfigApi::SaveParams), shared req: SetParams, res: StatusMessage, "Set robot params and save them to the robot's config");
+impl_api_request!(ReloadParamsRequest, ApiRequest::Config(ConfigApi::ReloadParams), res: StatusMessage, "Revert params set temporarily to the robot's saved config");
+impl_api_request!(Tag3dMappingRequest, ApiRequest::Config(ConfigApi::Tag3DMapping), res: StatusMessage, "Start mapping with 3D QR tags");
+impl_api_request!(ClearOdometryRequest, ApiRequest::Config(ConfigApi::ClearOdo), res: StatusMessage, "Reset the mileage and running time counters");
+impl_api_request!(LockControlRequest, ApiRequest::Config(ConfigApi::Lock), req: LockControl, res: StatusMessage, "Take control of the robot, required before control and navigation commands");
+impl_api_request!(UnlockControlRequest, ApiRequest::Config(ConfigApi::Unlock), res: StatusMessage, "Release control of the robot");
+impl_api_request!(ConfigurePushRequest, ApiRequest::Config(ConfigApi::Push), req: PushConfig, res: StatusMessage);
+impl_api_request!(SetThirdPartyErrorRequest, ApiRequest::Config(ConfigApi::SetError), req: ThirdPartyError, res: StatusMessage);
+impl_api_request!(ClearThirdPartyErrorRequest, ApiRequest::Config(ConfigApi::ClearError), req: ClearThirdPartyError, res: StatusMessage);
+impl_api_request!(SetThirdPartyWarningRequest, ApiRequest::Config(ConfigApi::SetWarning), shared req: ThirdPartyError, res: StatusMessage);
+impl_api_request!(ClearThirdPartyWarningRequest, ApiRequest::Config(ConfigApi::ClearWarning), shared req: ClearThirdPartyError, res: StatusMessage);
+impl_api_request!(DownloadScriptRequest, ApiRequest::Config(ConfigApi::DownloadScript), req: ScriptName, res: ScriptContent);
+impl_api_request!(RemoveScriptRequest, ApiRequest::Config(ConfigApi::RemoveScript), shared req: ScriptName, res: StatusMessage);
+impl_api_request!(ResetGnssRequest, ApiRequest::Config(ConfigApi::ResetGnss), res: StatusMessage);
+impl_api_request!(SetGnssBaudrateRequest, ApiRequest::Config(ConfigApi::SetGnssBaudrate), req: SetGnssBaudrate, res: StatusMessage);
 
 // Peripheral API requests
+impl_api_request!(PlayAudioRequest, ApiRequest::Peripheral(PeripheralApi::PlayAudio), req: PlayAudio, res: StatusMessage);
+impl_api_request!(StopAudioRequest, ApiRequest::Peripheral(PeripheralApi::StopAudio), res: StatusMessage);
+impl_api_request!(SoftEmcRequest, ApiRequest::Peripheral(PeripheralApi::SoftEmc), req: SoftEmc, res: StatusMessage, "Engage or release the software emergency stop");
+impl_api_request!(SetChargingRelayRequest, ApiRequest::Peripheral(PeripheralApi::SetChargingRelay), req: SetChargingRelay, res: StatusMessage);
 impl_api_request!(LoadJackRequest, ApiRequest::Peripheral(PeripheralApi::JackLoad), res: StatusMessage);
 impl_api_request!(UnloadJackRequest, ApiRequest::Peripheral(PeripheralApi::JackUnload), res: StatusMessage);
 impl_api_request!(StopJackRequest, ApiRequest::Peripheral(PeripheralApi::JackStop), res: StatusMessage);
 impl_api_request!(SetJackHeightRequest, ApiRequest::Peripheral(PeripheralApi::JackSetHeight), req: SetJackHeight, res: StatusMessage);
+impl_api_request!(WriteModbusRequest, ApiRequest::Peripheral(PeripheralApi::SetModbus), req: WriteModbus, res: StatusMessage);
+impl_api_request!(ClearGoodsRequest, ApiRequest::Peripheral(PeripheralApi::ClearGoods), req: ClearGoods, res: StatusMessage);
+impl_api_request!(ClearContainerRequest, ApiRequest::Peripheral(PeripheralApi::ClearContainer), req: ClearContainer, res: StatusMessage);
+impl_api_request!(ClearAllContainersGoodsRequest, ApiRequest::Peripheral(PeripheralApi::ClearAllContainersGoods), res: StatusMessage);
+impl_api_request!(SetContainerGoodsRequest, ApiRequest::Peripheral(PeripheralApi::SetContainerGoods), req: SetContainerGoods, res: StatusMessage);
+impl_api_request!(StartCalibrationRequest, ApiRequest::Peripheral(PeripheralApi::Calibrate), req: StartCalibration, res: StatusMessage);
+impl_api_request!(CancelCalibrationRequest, ApiRequest::Peripheral(PeripheralApi::EndCalibrate), res: StatusMessage);
+impl_api_request!(CalibrationResultRequest, ApiRequest::Peripheral(PeripheralApi::CalibResult), res: CalibrationResult);
+impl_api_request!(BinDetectRequest, ApiRequest::Peripheral(PeripheralApi::BinDetect), req: BinDetect, res: BinDetectResult);
+impl_api_request!(ReplayRequest, ApiRequest::Peripheral(PeripheralApi::Replay), res: StatusMessage);
+
+/// Upload a robot script
+///
+/// The script content is treated as sensitive and redacted in logs.
+///
+/// ```
+/// use seersdk_rs::UploadScript;
+///
+/// let request = UploadScript::new("blink.lua", "print('hello')").into_request();
+/// ```
+#[derive(Debug, Clone)]
+pub struct UploadScriptRequest {
+    pub req_body: UploadScript,
+}
+
+impl UploadScriptRequest {
+    pub fn new(req_body: UploadScript) -> Self {
+        Self { req_body }
+    }
+}
+
+impl UploadScript {
+    pub fn into_request(self) -> UploadScriptRequest {
+        UploadScriptRequest { req_body: self }
+    }
+}
+
+impl ToRequestBody for UploadScriptRequest {
+    fn to_request_body(
+        &self,
+    ) -> Result<std::borrow::Cow<'static, str>, serde_json::Error> {
+        serde_json::to_string(&self.req_body).map(std::borrow::Cow::Owned)
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::Config(ConfigApi::UploadScript)
+    }
+
+    fn sensitive(&self) -> bool {
+        true
+    }
+}
+
+impl FromResponseBody for UploadScriptRequest {
+    type Response = StatusMessage;
+}
+
+/// Drive the roller (belt) of a conveyor robot
+///
+/// ```
+/// use seersdk_rs::{RollerOperationRequest, RollerOp, RollerParams};
+///
+/// let request = RollerOperationRequest::new(RollerOp::FrontLoad)
+///     .with_params(RollerParams::new().with_speed(0.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollerOperationRequest {
+    pub op: RollerOp,
+    pub params: Option<RollerParams>,
+}
+
+impl RollerOperationRequest {
+    pub fn new(op: RollerOp) -> Self {
+        Self { op, params: None }
+    }
+
+    pub fn with_params(mut self, params: RollerParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+}
+
+impl ToRequestBody for RollerOperationRequest {
+    fn to_request_body(
+        &self,
+    ) -> Result<std::borrow::Cow<'static, str>, serde_json::Error> {
+        match self.params {
+            Some(ref params) => {
+                serde_json::to_string(params).map(std::borrow::Cow::Owned)
+            }
+            None => Ok(std::borrow::Cow::Borrowed("")),
+        }
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::Peripheral(self.op.into())
+    }
+}
+
+impl FromResponseBody for RollerOperationRequest {
+    type Response = StatusMessage;
+}
+
+/// Write opaque integrator data to the robot, the bytes are sent unmodified
+#[derive(Debug, Clone)]
+pub struct WriteTransparentDataRequest {
+    pub req_body: TransparentData,
+}
+
+impl WriteTransparentDataRequest {
+    pub fn new(req_body: TransparentData) -> Self {
+        Self { req_body }
+    }
+}
+
+impl TransparentData {
+    pub fn into_request(self) -> WriteTransparentDataRequest {
+        WriteTransparentDataRequest { req_body: self }
+    }
+}
+
+impl ToRawRequestBody for WriteTransparentDataRequest {
+    fn to_raw_body(&self) -> std::borrow::Cow<'_, [u8]> {
+        std::borrow::Cow::Borrowed(&self.req_body.data)
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::Peripheral(PeripheralApi::UpdateTransparentData)
+    }
+}
+
+impl FromRawResponseBody for WriteTransparentDataRequest {
+    type Response = StatusMessage;
+
+    fn from_raw_response(
+        body: &[u8],
+    ) -> Result<Self::Response, serde_json::Error> {
+        serde_json::from_slice(body)
+    }
+}
+
+/// Query the opaque integrator data stored on the robot
+#[derive(Debug, Clone, Default)]
+pub struct QueryTransparentDataRequest;
+
+impl QueryTransparentDataRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ToRawRequestBody for QueryTransparentDataRequest {
+    fn to_raw_body(&self) -> std::borrow::Cow<'_, [u8]> {
+        std::borrow::Cow::Borrowed(&[])
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::State(StateApi::TransparentData)
+    }
+}
+
+impl FromRawResponseBody for QueryTransparentDataRequest {
+    type Response = TransparentData;
+
+    fn from_raw_response(
+        body: &[u8],
+    ) -> Result<Self::Response, serde_json::Error> {
+        Ok(TransparentData {
+            data: body.to_vec(),
+        })
+    }
+}
+
+/// Download the robot model file
+#[derive(Debug, Clone, Default)]
+pub struct DownloadModelRequest;
+
+impl DownloadModelRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ToRawRequestBody for DownloadModelRequest {
+    fn to_raw_body(&self) -> std::borrow::Cow<'_, [u8]> {
+        std::borrow::Cow::Borrowed(&[])
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::State(StateApi::Model)
+    }
+}
+
+impl FromRawResponseBody for DownloadModelRequest {
+    type Response = ModelFile;
+
+    fn from_raw_response(
+        body: &[u8],
+    ) -> Result<Self::Response, serde_json::Error> {
+        Ok(ModelFile {
+            data: body.to_vec(),
+        })
+    }
+}
+
+/// Replace the robot model file, the bytes are sent unmodified
+#[derive(Debug, Clone)]
+pub struct UploadModelRequest {
+    pub req_body: ModelFile,
+}
+
+impl UploadModelRequest {
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            req_body: ModelFile::new(data),
+        }
+    }
+}
+
+impl ToRawRequestBody for UploadModelRequest {
+    fn to_raw_body(&self) -> std::borrow::Cow<'_, [u8]> {
+        std::borrow::Cow::Borrowed(&self.req_body.data)
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        ApiRequest::Config(ConfigApi::Model)
+    }
+}
+
+impl FromRawResponseBody for UploadModelRequest {
+    type Response = StatusMessage;
+
+    fn from_raw_response(
+        body: &[u8],
+    ) -> Result<Self::Response, serde_json::Error> {
+        serde_json::from_slice(body)
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u16)]
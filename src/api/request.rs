@@ -1,9 +1,129 @@
-use crate::{ApiRequest, PointId, TaskId};
+use std::borrow::Cow;
 
+use serde::Deserialize;
+
+use crate::{ApiRequest, FromResponseBody, PeripheralApi, PointId, TaskId};
+
+/// Request sent as a JSON body, see [`crate::RbkClient::request`]
+///
+/// Together with [`FromResponseBody`] this is what the client needs to send
+/// a typed request: the body is written to the port the API number belongs
+/// to, the response body is deserialized into
+/// [`FromResponseBody::Response`]. Both are usually generated with
+/// [`crate::impl_api_request!`].
 pub trait ToRequestBody {
     /// Convert the request to a JSON string body
-    fn to_request_body(&self) -> Result<String, serde_json::Error>;
+    ///
+    /// Requests without payload return a borrowed empty string, so polling
+    /// them does not allocate.
+    fn to_request_body(&self) -> Result<Cow<'static, str>, serde_json::Error>;
     fn to_api_request(&self) -> ApiRequest;
+
+    /// Whether the body carries sensitive data such as credentials or
+    /// script content, sensitive bodies are redacted in logs
+    fn sensitive(&self) -> bool {
+        false
+    }
+}
+
+/// Requests whose body is sent to the robot as-is instead of being
+/// serialized to JSON, used for integrator-defined binary payloads
+pub trait ToRawRequestBody {
+    fn to_raw_body(&self) -> Cow<'_, [u8]>;
+    fn to_api_request(&self) -> ApiRequest;
+}
+
+/// Object-safe view of a request, used to mix different request types in
+/// one collection (see [`crate::RbkClient::run_sequence`])
+///
+/// Implemented for every request implementing [`ToRequestBody`] and
+/// [`FromResponseBody`].
+pub trait DynRequest: Send + Sync {
+    fn api_request(&self) -> ApiRequest;
+
+    fn api_no(&self) -> u16 {
+        self.api_request().api_no()
+    }
+
+    /// Same as [`ToRequestBody::to_request_body`], named differently so
+    /// both traits can be glob-imported without ambiguity
+    fn request_body(&self) -> Result<Cow<'static, str>, serde_json::Error>;
+
+    /// Same as [`ToRequestBody::sensitive`]
+    fn is_sensitive(&self) -> bool;
+
+    /// Check the body against the typed response and return it as raw JSON
+    fn parse_response(
+        &self,
+        body: &[u8],
+    ) -> Result<serde_json::Value, serde_json::Error>;
+}
+
+impl<T> DynRequest for T
+where
+    T: ToRequestBody + FromResponseBody + Send + Sync,
+{
+    fn api_request(&self) -> ApiRequest {
+        self.to_api_request()
+    }
+
+    fn request_body(&self) -> Result<Cow<'static, str>, serde_json::Error> {
+        self.to_request_body()
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.sensitive()
+    }
+
+    fn parse_response(
+        &self,
+        body: &[u8],
+    ) -> Result<serde_json::Value, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_slice(body)?;
+        T::Response::deserialize(&value)?;
+        Ok(value)
+    }
+}
+
+/// Send a typed request but receive the response as raw JSON
+///
+/// The body, API number and port still come from `T`, only the response
+/// is deserialized into a [`serde_json::Value`] instead of `T::Response`.
+/// Handy for reading fields the typed response doesn't model.
+///
+/// ```no_run
+/// use seersdk_rs::{AsValue, BatteryStatusRequest, RbkClient};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = RbkClient::new("192.168.8.114");
+/// let battery = client
+///     .request(AsValue(BatteryStatusRequest::new()), Duration::from_secs(5))
+///     .await?;
+///
+/// println!("Battery level: {}", battery["battery_level"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsValue<T>(pub T);
+
+impl<T: ToRequestBody> ToRequestBody for AsValue<T> {
+    fn to_request_body(&self) -> Result<Cow<'static, str>, serde_json::Error> {
+        self.0.to_request_body()
+    }
+
+    fn to_api_request(&self) -> ApiRequest {
+        self.0.to_api_request()
+    }
+
+    fn sensitive(&self) -> bool {
+        self.0.sensitive()
+    }
+}
+
+impl<T: ToRequestBody> FromResponseBody for AsValue<T> {
+    type Response = serde_json::Value;
 }
 
 pub const SELF_POSITION: &str = "SELF_POSITION";
@@ -11,6 +131,9 @@ pub const SELF_POSITION: &str = "SELF_POSITION";
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
 pub struct MoveToPoint {
     pub id: PointId,
+    /// Starting Station Name, omitted when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<PointId>,
     pub x: Option<f64>,
     pub y: Option<f64>,
 
@@ -41,6 +164,11 @@ impl MoveToPoint {
             ..Default::default()
         }
     }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source_id = Some(source.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -84,7 +212,12 @@ pub struct MoveToTarget {
     /// Starting Station Name
     /// When the starting position is not at a station but the current position of the robot, its
     /// value is fixed as "SELF_POSITION"
-    #[serde(rename = "source_id")]
+    /// Omitted from the request when empty
+    #[serde(
+        rename = "source_id",
+        default,
+        skip_serializing_if = "String::is_empty"
+    )]
     pub start: PointId,
 
     /// Unique Task ID
@@ -180,6 +313,455 @@ impl SetJackHeight {
     }
 }
 
+/// Engage or release the charging relay
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SetChargingRelay {
+    /// `true` closes the relay and starts charging
+    pub status: bool,
+}
+
+impl SetChargingRelay {
+    pub fn new(status: bool) -> Self {
+        Self { status }
+    }
+}
+
+/// Engage or release the software emergency stop
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SoftEmc {
+    /// `true` stops the robot until released with `false`
+    pub status: bool,
+}
+
+impl SoftEmc {
+    pub fn new(status: bool) -> Self {
+        Self { status }
+    }
+}
+
+/// Read registers of a PLC attached to the robot
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReadModbus {
+    /// Address of the first register
+    pub addr: u32,
+    /// Number of registers to read
+    pub count: u32,
+}
+
+impl ReadModbus {
+    pub fn new(addr: u32, count: u32) -> Self {
+        Self { addr, count }
+    }
+}
+
+/// Write consecutive registers of a PLC attached to the robot
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WriteModbus {
+    /// Address of the first register
+    pub addr: u32,
+    pub values: Vec<u16>,
+}
+
+impl WriteModbus {
+    pub fn new(addr: u32, values: impl IntoIterator<Item = u16>) -> Self {
+        Self {
+            addr,
+            values: values.into_iter().collect(),
+        }
+    }
+}
+
+/// Error or warning raised by an external system, reported through the
+/// robot's alarm state
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ThirdPartyError {
+    /// Error code, unique per external system
+    pub code: u32,
+    /// Description shown with the alarm
+    pub msg: String,
+    /// Optional severity level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<u32>,
+}
+
+impl ThirdPartyError {
+    pub fn new(code: u32, msg: impl Into<String>) -> Self {
+        Self {
+            code,
+            msg: msg.into(),
+            level: None,
+        }
+    }
+
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = Some(level);
+        self
+    }
+}
+
+/// Clear a previously raised third-party error or warning by its code
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClearThirdPartyError {
+    pub code: u32,
+}
+
+impl ClearThirdPartyError {
+    pub fn new(code: u32) -> Self {
+        Self { code }
+    }
+}
+
+/// Bind goods to one of the robot's containers
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SetContainerGoods {
+    #[serde(rename = "container_name")]
+    pub container: String,
+    #[serde(rename = "goods_id")]
+    pub goods: String,
+}
+
+impl SetContainerGoods {
+    pub fn new(container: impl Into<String>, goods: impl Into<String>) -> Self {
+        Self {
+            container: container.into(),
+            goods: goods.into(),
+        }
+    }
+}
+
+/// Unbind the given goods from whichever container holds them
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClearGoods {
+    #[serde(rename = "goods_id")]
+    pub goods: String,
+}
+
+impl ClearGoods {
+    pub fn new(goods: impl Into<String>) -> Self {
+        Self {
+            goods: goods.into(),
+        }
+    }
+}
+
+/// Unbind the goods held by the given container
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClearContainer {
+    #[serde(rename = "container_name")]
+    pub container: String,
+}
+
+impl ClearContainer {
+    pub fn new(container: impl Into<String>) -> Self {
+        Self {
+            container: container.into(),
+        }
+    }
+}
+
+/// Run bin detection, optionally for a specific storage bin
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct BinDetect {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin_id: Option<String>,
+}
+
+impl BinDetect {
+    pub fn new() -> Self {
+        Self { bin_id: None }
+    }
+
+    pub fn with_bin_id(mut self, bin_id: impl Into<String>) -> Self {
+        self.bin_id = Some(bin_id.into());
+        self
+    }
+}
+
+/// Robot script to upload, replaces a script with the same name
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadScript {
+    pub name: String,
+    /// Script source code
+    pub content: String,
+}
+
+impl UploadScript {
+    pub fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Name of a robot script to download or remove
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScriptName {
+    pub name: String,
+}
+
+impl ScriptName {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+/// Move the robotic arm to a joint configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ArmMove {
+    /// Target joint positions in radians
+    pub joints: Vec<f64>,
+    /// Speed as a fraction of the maximum joint speed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+
+impl ArmMove {
+    pub fn new(joints: impl IntoIterator<Item = f64>) -> Self {
+        Self {
+            joints: joints.into_iter().collect(),
+            speed: None,
+        }
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+}
+
+/// Play an audio file stored on the robot
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PlayAudio {
+    /// Name of the audio file
+    pub name: String,
+    /// Repeat until stopped
+    #[serde(rename = "loop", default, skip_serializing_if = "Option::is_none")]
+    pub looped: Option<bool>,
+}
+
+impl PlayAudio {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            looped: None,
+        }
+    }
+
+    pub fn with_loop(mut self, looped: bool) -> Self {
+        self.looped = Some(looped);
+        self
+    }
+}
+
+/// Start calibrating the robot
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StartCalibration {
+    /// Kind of calibration, see the robot's supported calibration list
+    pub calib_type: u32,
+}
+
+impl StartCalibration {
+    pub fn new(calib_type: u32) -> Self {
+        Self { calib_type }
+    }
+}
+
+/// Take control of the robot under the given nickname
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LockControl {
+    /// Name shown to other clients querying the current lock holder
+    pub nick_name: String,
+}
+
+impl LockControl {
+    pub fn new(nick_name: impl Into<String>) -> Self {
+        Self {
+            nick_name: nick_name.into(),
+        }
+    }
+}
+
+/// Parameter values to set, by plugin and parameter name
+///
+/// # Example
+///
+/// ```
+/// use seersdk_rs::SetParams;
+///
+/// let params = SetParams::new().with_param("MoveFactory", "maxSpeed", 0.5);
+/// ```
+#[derive(
+    Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+pub struct SetParams {
+    #[serde(flatten)]
+    pub plugins: std::collections::BTreeMap<
+        String,
+        serde_json::Map<String, serde_json::Value>,
+    >,
+}
+
+impl SetParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_param(
+        mut self,
+        plugin: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.plugins
+            .entry(plugin.into())
+            .or_default()
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
+/// Declare which APIs the robot pushes and how often
+///
+/// ```
+/// use seersdk_rs::PushConfig;
+///
+/// let config = PushConfig::new(19301)
+///     .with_apis([1004, 1007])
+///     .with_interval(200);
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PushConfig {
+    /// Port the pushed data is sent to
+    pub port: u16,
+    /// APIs whose data is included in each push
+    pub api_list: Vec<u16>,
+    /// Push interval in milliseconds
+    #[serde(rename = "interval")]
+    pub interval_ms: u32,
+}
+
+impl PushConfig {
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            api_list: Vec::new(),
+            interval_ms: 1000,
+        }
+    }
+
+    pub fn with_apis(mut self, apis: impl IntoIterator<Item = u16>) -> Self {
+        self.api_list = apis.into_iter().collect();
+        self
+    }
+
+    pub fn with_interval(mut self, interval_ms: u32) -> Self {
+        self.interval_ms = interval_ms;
+        self
+    }
+}
+
+/// Roller (belt) operation, each maps to its own peripheral API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollerOp {
+    FrontRoll,
+    BackRoll,
+    LeftRoll,
+    RightRoll,
+    FrontLoad,
+    FrontUnload,
+    FrontPreLoad,
+    BackLoad,
+    BackUnload,
+    BackPreLoad,
+    LeftLoad,
+    LeftUnload,
+    RightLoad,
+    RightUnload,
+    LeftPreLoad,
+    RightPreLoad,
+    Stop,
+    LeftRightInverse,
+    FrontBackInverse,
+}
+
+impl From<RollerOp> for PeripheralApi {
+    fn from(op: RollerOp) -> Self {
+        match op {
+            RollerOp::FrontRoll => PeripheralApi::RollerFrontRoll,
+            RollerOp::BackRoll => PeripheralApi::RollerBackRoll,
+            RollerOp::LeftRoll => PeripheralApi::RollerLeftRoll,
+            RollerOp::RightRoll => PeripheralApi::RollerRightRoll,
+            RollerOp::FrontLoad => PeripheralApi::RollerFrontLoad,
+            RollerOp::FrontUnload => PeripheralApi::RollerFrontUnload,
+            RollerOp::FrontPreLoad => PeripheralApi::RollerFrontPreLoad,
+            RollerOp::BackLoad => PeripheralApi::RollerBackLoad,
+            RollerOp::BackUnload => PeripheralApi::RollerBackUnload,
+            RollerOp::BackPreLoad => PeripheralApi::RollerBackPreLoad,
+            RollerOp::LeftLoad => PeripheralApi::RollerLeftLoad,
+            RollerOp::LeftUnload => PeripheralApi::RollerLeftUnload,
+            RollerOp::RightLoad => PeripheralApi::RollerRightLoad,
+            RollerOp::RightUnload => PeripheralApi::RollerRightUnload,
+            RollerOp::LeftPreLoad => PeripheralApi::RollerLeftPreLoad,
+            RollerOp::RightPreLoad => PeripheralApi::RollerRightPreLoad,
+            RollerOp::Stop => PeripheralApi::RollerStop,
+            RollerOp::LeftRightInverse => PeripheralApi::RollerLeftRightInverse,
+            RollerOp::FrontBackInverse => PeripheralApi::RollerFrontBackInverse,
+        }
+    }
+}
+
+/// Optional parameters of a roller operation
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct RollerParams {
+    /// Roller speed in m/s
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    /// Duration of the operation in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+}
+
+impl RollerParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SetGnssBaudrate {
+    /// Serial baudrate of the GNSS receiver
+    pub baudrate: u32,
+}
+
+impl SetGnssBaudrate {
+    pub fn new(baudrate: u32) -> Self {
+        Self { baudrate }
+    }
+}
+
+/// Opaque integrator-defined bytes passed through the robot
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransparentData {
+    pub data: Vec<u8>,
+}
+
+impl TransparentData {
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self { data: data.into() }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MoveDesignedPath {
     #[serde(rename = "move_task_list")]
@@ -194,20 +776,93 @@ impl MoveDesignedPath {
     }
 }
 
+/// Cancel a single task of a designed path, the robot decelerates first
+/// and continues with the remaining tasks
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SafeClearMovements {
+    pub task_id: TaskId,
+}
+
+impl SafeClearMovements {
+    pub fn new(task_id: impl Into<TaskId>) -> Self {
+        Self {
+            task_id: task_id.into(),
+        }
+    }
+}
+
+/// Query the path the robot would take to a target
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GetTargetPath {
+    /// Target station
+    pub id: String,
+    /// Station to start from, the current position if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+}
+
+impl GetTargetPath {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            source_id: None,
+        }
+    }
+
+    pub fn with_source(mut self, source_id: impl Into<String>) -> Self {
+        self.source_id = Some(source_id.into());
+        self
+    }
+}
+
+/// Query the status of navigation tasks
+///
+/// The robot answers differently depending on how the task ids are given:
+///
+/// ```
+/// use seersdk_rs::GetTaskStatus;
+///
+/// // Only the listed tasks
+/// let query = GetTaskStatus::for_ids(["task_001", "task_002"]);
+/// // Nothing at all
+/// let query = GetTaskStatus::empty();
+/// // The most recently completed task and all incomplete ones
+/// let query = GetTaskStatus::recent();
+/// ```
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct GetTaskStatus {
     ///Specify the task_id of the task to be queried in the array.
     ///If the array is empty, the response will also be empty;
     ///If this field is omitted, the status of the most recently completed task and the status of all incomplete tasks of the robot will be returned.
-    pub task_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_ids: Option<Vec<String>>,
 }
 
-impl FromIterator<String> for GetTaskStatus {
-    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+impl GetTaskStatus {
+    /// Query only the tasks with the given ids
+    pub fn for_ids(ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
         Self {
-            task_ids: iter.into_iter().collect(),
+            task_ids: Some(ids.into_iter().map(Into::into).collect()),
         }
     }
+
+    /// Query no task, serialized as `{"task_ids": []}`
+    pub fn empty() -> Self {
+        Self {
+            task_ids: Some(Vec::new()),
+        }
+    }
+
+    /// Query the most recently completed task and all incomplete tasks
+    pub fn recent() -> Self {
+        Self { task_ids: None }
+    }
+}
+
+impl FromIterator<String> for GetTaskStatus {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self::for_ids(iter)
+    }
 }
 
 #[cfg(test)]
@@ -263,4 +918,60 @@ mod tests {
         let m2 = serde_json::from_str::<MoveToTarget>(&serialized).unwrap();
         assert_eq!(m1, m2);
     }
+
+    #[test]
+    fn test_source_id_serialization() {
+        let target = MoveToTarget::new("AP1").with_start("LM2".to_string());
+        let body = serde_json::to_value(&target).unwrap();
+        assert_eq!(body["source_id"], "LM2");
+
+        let target = MoveToTarget {
+            target: "AP1".to_string(),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(&target).unwrap();
+        assert!(body.get("source_id").is_none());
+
+        let point = MoveToPoint::new(1.0, 2.0).with_source("LM2");
+        let body = serde_json::to_value(&point).unwrap();
+        assert_eq!(body["source_id"], "LM2");
+
+        let body = serde_json::to_value(MoveToPoint::zeros()).unwrap();
+        assert!(body.get("source_id").is_none());
+    }
+
+    #[test]
+    fn test_push_config_serialization() {
+        let config = PushConfig::new(19301)
+            .with_apis([1004, 1007])
+            .with_interval(200);
+        let body = serde_json::to_value(&config).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "port": 19301,
+                "api_list": [1004, 1007],
+                "interval": 200
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_task_status_modes() {
+        use serde_json::json;
+
+        let body =
+            serde_json::to_value(GetTaskStatus::for_ids(["t1", "t2"])).unwrap();
+        assert_eq!(body, json!({"task_ids": ["t1", "t2"]}));
+
+        let body = serde_json::to_value(GetTaskStatus::empty()).unwrap();
+        assert_eq!(body, json!({"task_ids": []}));
+
+        let body = serde_json::to_value(GetTaskStatus::recent()).unwrap();
+        assert_eq!(body, json!({}));
+
+        let query: GetTaskStatus = vec!["t1".to_string()].into_iter().collect();
+        assert_eq!(query.task_ids, Some(vec!["t1".to_string()]));
+    }
 }
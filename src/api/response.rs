@@ -1,17 +1,57 @@
 use crate::{PointId, RbkError, TaskId};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct StatusMessage {
-    #[serde(rename = "ret_code")]
+    /// A missing `ret_code` is treated as success
+    #[serde(rename = "ret_code", default)]
     pub code: StatusCode,
     #[serde(rename = "err_msg", default)]
     pub message: String,
-    #[serde(rename = "create_on", default)]
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
     pub timestamp: Option<String>,
 }
 
 impl StatusMessage {
-    //fixme: to weird impl
+    /// When the robot created the response, parsed from `create_on`
+    ///
+    /// Understands Unix timestamps in seconds, optionally with a fraction
+    /// (`"1700000000"`, `"1700000000.25"`), other formats give `None`.
+    ///
+    /// ```
+    /// use seersdk_rs::StatusMessage;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let status: StatusMessage =
+    ///     serde_json::from_str(r#"{"ret_code": 0, "create_on": "1700000000"}"#)
+    ///         .unwrap();
+    /// assert_eq!(
+    ///     status.created_at(),
+    ///     Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    /// );
+    /// ```
+    pub fn created_at(&self) -> Option<SystemTime> {
+        let secs: f64 = self.timestamp.as_deref()?.trim().parse().ok()?;
+        UNIX_EPOCH.checked_add(Duration::try_from_secs_f64(secs).ok()?)
+    }
+
+    /// Convert the robot's status into a `Result`
+    ///
+    /// ```no_run
+    /// # use seersdk_rs::{RbkClient, StopExerciseRequest};
+    /// # use std::time::Duration;
+    /// # async fn example(client: RbkClient) -> Result<(), seersdk_rs::RbkError> {
+    /// client
+    ///     .request(StopExerciseRequest::new(), Duration::from_secs(5))
+    ///     .await?
+    ///     .into_result()?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn into_result(self) -> Result<(), RbkError> {
         if self.code == StatusCode::Success {
             return Ok(());
@@ -25,6 +65,84 @@ impl StatusMessage {
     }
 }
 
+impl HasStatus for StatusMessage {
+    fn status_code(&self) -> Option<StatusCode> {
+        Some(self.code)
+    }
+}
+
+/// Responses carrying the robot's `ret_code`
+///
+/// Lets generic code check whether the robot reported success without
+/// caring whether the code was sent or omitted.
+///
+/// ```
+/// use seersdk_rs::{BatteryStatus, HasStatus};
+///
+/// let status: BatteryStatus = serde_json::from_str(
+///     r#"{"battery_level": 0.8, "battery_temp": 30.0, "charging": false,
+///         "voltage": 48.0, "current": 1.5}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(status.status_code(), None);
+/// assert!(status.ok());
+/// ```
+pub trait HasStatus {
+    /// The reported code, `None` if the robot omitted it
+    fn status_code(&self) -> Option<StatusCode>;
+
+    /// Whether the robot reported success, an omitted code counts as
+    /// success
+    fn ok(&self) -> bool {
+        matches!(self.status_code(), None | Some(StatusCode::Success))
+    }
+}
+
+impl TryFrom<StatusMessage> for () {
+    type Error = RbkError;
+
+    fn try_from(status: StatusMessage) -> Result<Self, Self::Error> {
+        status.into_result()
+    }
+}
+
+/// Implements `into_result` for responses which embed `ret_code`/`err_msg`
+/// next to their payload. On success the response itself is returned,
+/// a missing code is treated as success. Responses whose code is not
+/// optional are marked with `required_code`.
+macro_rules! impl_into_result {
+    ($res_type:ty, required_code) => {
+        impl_into_result!(@impl $res_type, [Some],);
+    };
+    ($res_type:ty $(, timestamp: $ts_field:ident)?) => {
+        impl_into_result!(@impl $res_type, [], $($ts_field)?);
+    };
+    (@impl $res_type:ty, [$($wrap:ident)?], $($ts_field:ident)?) => {
+        impl $res_type {
+            pub fn into_result(self) -> Result<Self, RbkError> {
+                let code = match $($wrap)?(self.code) {
+                    None | Some(StatusCode::Success) => return Ok(self),
+                    Some(code) => code,
+                };
+
+                let timestamp: Option<String> = None $(.or(self.$ts_field))?;
+
+                Err(RbkError::BadResponse {
+                    code,
+                    message: self.message,
+                    timestamp,
+                })
+            }
+        }
+
+        impl HasStatus for $res_type {
+            fn status_code(&self) -> Option<StatusCode> {
+                $($wrap)?(self.code)
+            }
+        }
+    };
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
 #[repr(u32)]
 pub enum StatusCode {
@@ -79,6 +197,54 @@ pub enum StatusCode {
     Custom,
 }
 
+// `#[default]` would clash with the `num_enum(default)` fallback above
+#[allow(clippy::derivable_impls)]
+impl Default for StatusCode {
+    fn default() -> Self {
+        StatusCode::Success
+    }
+}
+
+/// Deserialize a `u32` sent either as a number or, by some firmware
+/// versions, as a numeric string such as `"40001"`
+fn u32_or_string<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct U32OrString;
+
+    impl serde::de::Visitor<'_> for U32OrString {
+        type Value = u32;
+
+        fn expecting(
+            &self,
+            f: &mut std::fmt::Formatter<'_>,
+        ) -> std::fmt::Result {
+            f.write_str("an unsigned integer or a numeric string")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<u32, E> {
+            u32::try_from(v).map_err(|_| {
+                E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+            })
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<u32, E> {
+            u32::try_from(v).map_err(|_| {
+                E::invalid_value(serde::de::Unexpected::Signed(v), &self)
+            })
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<u32, E> {
+            v.trim().parse().map_err(|_| {
+                E::invalid_value(serde::de::Unexpected::Str(v), &self)
+            })
+        }
+    }
+
+    deserializer.deserialize_any(U32OrString)
+}
+
 /// Assumed that the enum is represented as u32
 macro_rules! impl_serde_for_num_enum {
     ($enum_type:ty) => {
@@ -87,7 +253,7 @@ macro_rules! impl_serde_for_num_enum {
             where
                 D: serde::Deserializer<'de>,
             {
-                let code = u32::deserialize(deserializer)?;
+                let code = u32_or_string(deserializer)?;
                 Ok(<$enum_type>::from(code))
             }
         }
@@ -106,11 +272,232 @@ macro_rules! impl_serde_for_num_enum {
 impl_serde_for_num_enum!(StatusCode);
 impl_serde_for_num_enum!(JackOperationStatus);
 
+/// Deserialize a `create_on` timestamp, which some firmware versions send
+/// as a number instead of a string
+fn timestamp_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(timestamp)) => Ok(Some(timestamp)),
+        Some(serde_json::Value::Number(timestamp)) => {
+            Ok(Some(timestamp.to_string()))
+        }
+        Some(other) => Err(serde::de::Error::invalid_type(
+            serde::de::Unexpected::Other(&other.to_string()),
+            &"a timestamp string or number",
+        )),
+    }
+}
+
+/// Deserialize user defined bytes sent either as an array of numbers or,
+/// by some firmware versions, as a string holding the raw text
+fn bytes_or_string<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct BytesOrString;
+
+    impl<'de> serde::de::Visitor<'de> for BytesOrString {
+        type Value = Vec<u8>;
+
+        fn expecting(
+            &self,
+            f: &mut std::fmt::Formatter<'_>,
+        ) -> std::fmt::Result {
+            f.write_str("an array of bytes or a string")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+            Ok(v.as_bytes().to_vec())
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Vec<u8>, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Vec<u8>, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    deserializer.deserialize_any(BytesOrString)
+}
+
+/// Deserialize a value sent either on its own or as an array of values,
+/// `null` gives an empty list
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(OneOrMany::Many(values)) => values,
+        Some(OneOrMany::One(value)) => vec![value],
+    })
+}
+
+/// Lift the status of a response from newer firmware, which nests it as
+/// `{"header": {"ret_code": .., "err_msg": ..}, "data": {..}}`, to the flat
+/// layout the response structs expect
+///
+/// The fields of `data` are moved up next to the status. Returns whether
+/// there was a header, a flat response is left as it is.
+pub(crate) fn flatten_status_header(response: &mut serde_json::Value) -> bool {
+    let Some(fields) = response.as_object_mut() else {
+        return false;
+    };
+    let Some(serde_json::Value::Object(header)) = fields.get("header") else {
+        return false;
+    };
+    if !header.contains_key("ret_code") && !header.contains_key("err_msg") {
+        return false;
+    }
+
+    let Some(serde_json::Value::Object(header)) = fields.remove("header")
+    else {
+        unreachable!("checked above");
+    };
+    if let Some(serde_json::Value::Object(_)) = fields.get("data")
+        && let Some(serde_json::Value::Object(data)) = fields.remove("data")
+    {
+        for (name, value) in data {
+            fields.entry(name).or_insert(value);
+        }
+    }
+    // The header takes precedence over flat status fields
+    fields.extend(header);
+
+    true
+}
+
+/// Deserialize a section flattened into a combined status, `None` if the
+/// robot sent none of the section's `keys`
+fn flat_section<'de, D, T>(
+    deserializer: D,
+    keys: &[&str],
+) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    use serde::Deserialize;
+
+    let map = serde_json::Map::deserialize(deserializer)?;
+    if !keys.iter().any(|key| map.contains_key(*key)) {
+        return Ok(None);
+    }
+    T::deserialize(serde_json::Value::Object(map))
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn speed_section<'de, D>(
+    deserializer: D,
+) -> Result<Option<RobotSpeed>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    flat_section(deserializer, &["vx", "vy", "w"])
+}
+
+fn area_section<'de, D>(deserializer: D) -> Result<Option<RobotArea>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    flat_section(deserializer, &["area_ids", "max_speed"])
+}
+
+/// Deserialize a boolean sent either as `true`/`false` or, by some
+/// firmware versions, as `1`/`0`
+fn bool_or_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct BoolOrInt;
+
+    impl serde::de::Visitor<'_> for BoolOrInt {
+        type Value = bool;
+
+        fn expecting(
+            &self,
+            f: &mut std::fmt::Formatter<'_>,
+        ) -> std::fmt::Result {
+            f.write_str("a boolean or 0/1")
+        }
+
+        fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<bool, E> {
+            Ok(v)
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<bool, E> {
+            match v {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(
+                    serde::de::Unexpected::Unsigned(v),
+                    &self,
+                )),
+            }
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<bool, E> {
+            match v {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(
+                    serde::de::Unexpected::Signed(v),
+                    &self,
+                )),
+            }
+        }
+    }
+
+    deserializer.deserialize_any(BoolOrInt)
+}
+
+/// Response type of a request, the counterpart of
+/// [`crate::ToRequestBody`]
+///
+/// The robot's JSON body is deserialized into `Response` as-is, a non-zero
+/// `ret_code` is not an error at this point. Responses usually implement
+/// `into_result()` to turn it into one.
 pub trait FromResponseBody: Sized {
     type Response: serde::de::DeserializeOwned;
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Counterpart of [`FromResponseBody`] for requests sent through
+/// [`crate::RbkClient::request_raw`], the response body is handed over
+/// without assuming it is UTF-8 JSON
+pub trait FromRawResponseBody: Sized {
+    type Response;
+
+    fn from_raw_response(
+        body: &[u8],
+    ) -> Result<Self::Response, serde_json::Error>;
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct CommonInfo {
     pub id: String,
     pub version: String,
@@ -121,22 +508,33 @@ pub struct CommonInfo {
     pub message: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+impl_into_result!(CommonInfo);
+
+/// Running information of the robot
+///
+/// The robot reports two uptimes: `total` is the time since the controller
+/// was last started, `total_time` adds up the time over every start.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct OperationInfo {
+    /// Distance driven over the robot's life, in meters
     #[serde(rename = "odo")]
     pub mileage: f64,
+    /// Time since the controller was last started, in milliseconds
     #[serde(rename = "total")]
     pub session_time_ms: f64,
+    /// Time the controller has been running over the robot's life, in
+    /// milliseconds
     #[serde(rename = "total_time")]
     pub total_time_ms: f64,
-    /// Controller temperature in Celsius
-    pub controller_temp: f64,
-    /// Controller humidity in percentage
+    /// Controller temperature in Celsius, not reported by every firmware
     #[serde(default)]
-    pub controller_humi: f64,
-    /// Controller voltage in Volts
+    pub controller_temp: Option<f64>,
+    /// Controller humidity in percentage, not reported by every firmware
     #[serde(default)]
-    pub controller_voltage: f64,
+    pub controller_humi: Option<f64>,
+    /// Controller voltage in Volts, not reported by every firmware
+    #[serde(default)]
+    pub controller_voltage: Option<f64>,
 
     #[serde(rename = "ret_code", default)]
     pub code: Option<StatusCode>,
@@ -144,7 +542,26 @@ pub struct OperationInfo {
     pub message: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+impl_into_result!(OperationInfo);
+
+impl OperationInfo {
+    /// Time since the controller was last started
+    pub fn session_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.session_time_ms.max(0.0) / 1000.0)
+    }
+
+    /// Time the controller has been running over the robot's life
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.total_time_ms.max(0.0) / 1000.0)
+    }
+
+    /// Distance driven over the robot's life, in meters
+    pub fn odometry_meters(&self) -> f64 {
+        self.mileage
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct RobotPose {
     /// X coordinate in meters
     pub x: f64,
@@ -162,6 +579,77 @@ pub struct RobotPose {
     pub message: String,
 }
 
+impl_into_result!(RobotPose);
+
+/// Current velocity of the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RobotSpeed {
+    /// Speed along the robot's X axis in m/s
+    #[serde(default)]
+    pub vx: f64,
+    /// Speed along the robot's Y axis in m/s
+    #[serde(default)]
+    pub vy: f64,
+    /// Angular speed in rad/s
+    #[serde(default)]
+    pub w: f64,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(RobotSpeed);
+
+/// Map areas the robot is currently in
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RobotArea {
+    #[serde(default)]
+    pub area_ids: Vec<String>,
+    /// Speed limit imposed by the current area in m/s, `None` if the area
+    /// does not limit the speed
+    #[serde(default)]
+    pub max_speed: Option<f64>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(RobotArea);
+
+/// Localization state reported by the robot
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, num_enum::FromPrimitive,
+)]
+#[repr(u32)]
+pub enum RelocState {
+    /// Also reported before the first relocation and after cancelling one
+    #[default]
+    Failed = 0,
+    Success = 1,
+    Relocating = 2,
+    /// Relocation finished, waiting for the location to be confirmed
+    Completed = 3,
+}
+
+impl_serde_for_num_enum!(RelocState);
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RelocationStatus {
+    #[serde(rename = "reloc_status", default)]
+    pub state: RelocState,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(RelocationStatus);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
 #[repr(u8)]
 pub enum BlockReason {
@@ -207,30 +695,82 @@ impl serde::Serialize for BlockReason {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Block status of the robot
+///
+/// Firmware reports either a single `block_reason` or every concurrent
+/// obstruction, as an array in `block_reason` or in `block_reasons`.
+/// `reasons` holds all of them in any case, `reason` the first one.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "RawBlockStatus")]
 pub struct BlockStatus {
     #[serde(rename = "blocked")]
     pub is_blocked: bool,
-    #[serde(rename = "block_reason", default)]
+    #[serde(rename = "block_reason")]
     pub reason: Option<BlockReason>,
-    #[serde(rename = "block_x", default)]
+    /// Every reason the robot is blocked for
+    #[serde(rename = "block_reasons")]
+    pub reasons: Vec<BlockReason>,
+    #[serde(rename = "block_x")]
     pub x: Option<f64>,
-    #[serde(rename = "block_y", default)]
+    #[serde(rename = "block_y")]
     pub y: Option<f64>,
 
     #[serde(rename = "ret_code")]
     pub code: StatusCode,
-    #[serde(rename = "err_msg", default)]
+    #[serde(rename = "err_msg")]
     pub message: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+impl_into_result!(BlockStatus, required_code);
+
+/// [`BlockStatus`] as sent by the robot
+#[derive(serde::Deserialize)]
+struct RawBlockStatus {
+    #[serde(deserialize_with = "bool_or_int")]
+    blocked: bool,
+    #[serde(default, deserialize_with = "one_or_many")]
+    block_reason: Vec<BlockReason>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    block_reasons: Vec<BlockReason>,
+    #[serde(default)]
+    block_x: Option<f64>,
+    #[serde(default)]
+    block_y: Option<f64>,
+    /// Taken by the enclosing struct when flattened into [`AllStatus1`]
+    #[serde(default)]
+    ret_code: StatusCode,
+    #[serde(default)]
+    err_msg: String,
+}
+
+impl From<RawBlockStatus> for BlockStatus {
+    fn from(raw: RawBlockStatus) -> Self {
+        let reasons = if raw.block_reasons.is_empty() {
+            raw.block_reason
+        } else {
+            raw.block_reasons
+        };
+
+        BlockStatus {
+            is_blocked: raw.blocked,
+            reason: reasons.first().copied(),
+            reasons,
+            x: raw.block_x,
+            y: raw.block_y,
+            code: raw.ret_code,
+            message: raw.err_msg,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BatteryStatus {
     /// Level in range 0.0 to 1.0
     pub battery_level: f64,
     /// Temperature in Celsius
     pub battery_temp: f64,
     /// Is the robot currently charging
+    #[serde(deserialize_with = "bool_or_int")]
     pub charging: bool,
     /// Voltage in Volts
     pub voltage: f64,
@@ -243,110 +783,544 @@ pub struct BatteryStatus {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
-#[repr(u32)]
-pub enum JackOperationStatus {
-    Rising = 0x0,
-    RisingInPlace = 0x1,
-    Lowering = 0x2,
-    LoweringInPlace = 0x3,
-    Stop = 0x4,
-    #[num_enum(default)]
-    Failed = 0xFF,
-}
+impl_into_result!(BatteryStatus);
 
-/// Status of the robot's jack
-/// ### Example
-/// ```
-/// use seersdk_rs::{JackStatus, JackOperation};
-/// let raw_json = r#"
-///  {
-///   "jack_emc": false,
-///   "jack_enable": false,
-///   "jack_error_code": 0,
-///   "jack_height": 0,
-///   "jack_isFull": false,
-///   "jack_mode": false,
-///   "jack_speed": 0,
-///   "jack_state": 0,
-///   "peripheral_data": [],
-///   "ret_code": 0
-/// }"#;
-///
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct JackStatus {
-    /// Current mode is automatic or manual
-    #[serde(rename = "jack_mode")]
-    pub automatic_mode: bool,
+/// Reading of a single motor encoder
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EncoderReading {
+    /// Motor id
+    pub id: u32,
+    /// Accumulated encoder count
+    pub value: i64,
+    /// Current speed reported by the encoder
+    #[serde(default)]
+    pub speed: f64,
+}
 
-    #[serde(rename = "jack_enable")]
-    pub enabled: bool,
+/// Readings of all motor encoders
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EncoderStatus {
+    #[serde(default)]
+    pub encoders: Vec<EncoderReading>,
 
-    #[serde(rename = "jack_error_code")]
-    pub error_code: u32,
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
 
-    /// Current jack operation
-    #[serde(rename = "jack_state")]
-    pub operation: JackOperationStatus,
+impl_into_result!(EncoderStatus);
 
-    #[serde(rename = "jack_isFull")]
-    pub has_payload: bool,
-    /// Jacking speed in mm/s
-    #[serde(rename = "jack_speed")]
-    pub speed: u32,
-    /// Is emergency stop activated
-    #[serde(rename = "jack_emc")]
-    pub emergency_stop: bool,
-    /// Current height in meters
-    #[serde(rename = "jack_height")]
-    pub height: f64,
-    /// User defined peripheral data
-    #[serde(rename = "peripheral_data")]
-    pub peripheral_data: Vec<u8>,
+/// Orientation and motion measured by the robot's IMU
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ImuData {
+    /// Yaw in radians
+    #[serde(default)]
+    pub yaw: f64,
+    /// Roll in radians
+    #[serde(default)]
+    pub roll: f64,
+    /// Pitch in radians
+    #[serde(default)]
+    pub pitch: f64,
+    /// Acceleration along X in m/s²
+    #[serde(default)]
+    pub acc_x: f64,
+    /// Acceleration along Y in m/s²
+    #[serde(default)]
+    pub acc_y: f64,
+    /// Acceleration along Z in m/s²
+    #[serde(default)]
+    pub acc_z: f64,
+    /// Angular velocity around X in rad/s
+    #[serde(default)]
+    pub rot_x: f64,
+    /// Angular velocity around Y in rad/s
+    #[serde(default)]
+    pub rot_y: f64,
+    /// Angular velocity around Z in rad/s
+    #[serde(default)]
+    pub rot_z: f64,
 
     #[serde(rename = "ret_code", default)]
     pub code: Option<StatusCode>,
     #[serde(rename = "err_msg", default)]
     pub message: String,
+}
 
-    /// API Upload timestamp
-    #[serde(rename = "create_on", default)]
-    pub timestamp: Option<String>,
+impl_into_result!(ImuData);
+
+/// State of a single drive motor
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MotorInfo {
+    pub motor_name: String,
+    /// Position in the motor's own unit, e.g. encoder counts
+    #[serde(default)]
+    pub position: f64,
+    /// Speed in rpm
+    #[serde(default)]
+    pub speed: f64,
+    /// Current in amperes
+    #[serde(default)]
+    pub current: f64,
+    /// Voltage in volts
+    #[serde(default)]
+    pub voltage: f64,
+    /// Temperature in °C
+    #[serde(default)]
+    pub temperature: f64,
+    /// Driver error code, 0 if none
+    #[serde(default)]
+    pub error_code: u32,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct NavStatus {
-    #[serde(rename = "task_status")]
-    pub status: TaskStatus,
-    #[serde(rename = "task_type")]
-    pub ty: TaskType,
-    pub target_id: PointId,
-    /// Target point coordinates (x, y, angle)
-    pub target_point: [f64; 3],
+/// State of all drive motors
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MotorStatus {
+    #[serde(rename = "motor_info", default)]
+    pub motors: Vec<MotorInfo>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(MotorStatus);
+
+/// Result of a bin detection, offsets are relative to the expected pose
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BinDetectResult {
+    #[serde(deserialize_with = "bool_or_int")]
+    pub detected: bool,
+    /// Offset along X in meters
+    #[serde(default)]
+    pub offset_x: f64,
+    /// Offset along Y in meters
+    #[serde(default)]
+    pub offset_y: f64,
+    /// Angular offset in radians
+    #[serde(default)]
+    pub offset_angle: f64,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(BinDetectResult);
+
+/// Storage bin seen by the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BinInfo {
+    pub bin_id: String,
+    /// Whether the bin holds goods
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub filled: bool,
+}
+
+/// Storage bins seen by the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Bins {
+    #[serde(default)]
+    pub bins: Vec<BinInfo>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(Bins);
+
+/// A single scan of the robot's laser (lidar)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LidarData {
+    /// Angle of the first beam in radians
+    pub angle_min: f64,
+    /// Angle of the last beam in radians
+    pub angle_max: f64,
+    /// Angle between two beams in radians
+    pub angle_increment: f64,
+    /// Measured distance per beam in meters
+    #[serde(default)]
+    pub ranges: Vec<f64>,
+    /// Reflection intensity per beam, not reported by every laser
+    #[serde(default)]
+    pub intensities: Vec<f64>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl LidarData {
+    /// Angle in radians of the beam at the given index
+    pub fn angle_of(&self, index: usize) -> f64 {
+        self.angle_min + self.angle_increment * index as f64
+    }
+}
+
+impl_into_result!(LidarData);
+
+/// Holder of the robot's control lock
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CurrentLock {
+    /// Whether control is currently held by some client
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub locked: bool,
+    /// Nickname of the client holding control
+    #[serde(default)]
+    pub nick_name: String,
+    /// IP address of the client holding control
+    #[serde(default)]
+    pub ip: String,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(CurrentLock);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
+#[repr(u32)]
+pub enum JackOperationStatus {
+    Rising = 0x0,
+    RisingInPlace = 0x1,
+    Lowering = 0x2,
+    LoweringInPlace = 0x3,
+    Stop = 0x4,
+    #[num_enum(default)]
+    Failed = 0xFF,
+}
+
+/// Status of the robot's jack
+/// ### Example
+/// ```
+/// use seersdk_rs::{JackStatus, JackOperation};
+/// let raw_json = r#"
+///  {
+///   "jack_emc": false,
+///   "jack_enable": false,
+///   "jack_error_code": 0,
+///   "jack_height": 0,
+///   "jack_isFull": false,
+///   "jack_mode": false,
+///   "jack_speed": 0,
+///   "jack_state": 0,
+///   "peripheral_data": [],
+///   "ret_code": 0
+/// }"#;
+///
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JackStatus {
+    /// Current mode is automatic or manual
+    #[serde(rename = "jack_mode", deserialize_with = "bool_or_int")]
+    pub automatic_mode: bool,
+
+    #[serde(rename = "jack_enable", deserialize_with = "bool_or_int")]
+    pub enabled: bool,
+
+    #[serde(rename = "jack_error_code")]
+    pub error_code: u32,
+
+    /// Current jack operation
+    #[serde(rename = "jack_state")]
+    pub operation: JackOperationStatus,
+
+    #[serde(rename = "jack_isFull", deserialize_with = "bool_or_int")]
+    pub has_payload: bool,
+    /// Jacking speed in mm/s
+    #[serde(rename = "jack_speed")]
+    pub speed: u32,
+    /// Is emergency stop activated
+    #[serde(rename = "jack_emc", deserialize_with = "bool_or_int")]
+    pub emergency_stop: bool,
+    /// Current height in meters
+    #[serde(rename = "jack_height")]
+    pub height: f64,
+    /// User defined peripheral data, see [`JackStatus::peripheral_as`]
+    #[serde(
+        rename = "peripheral_data",
+        default,
+        deserialize_with = "bytes_or_string"
+    )]
+    pub peripheral_data: Vec<u8>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+
+    /// API Upload timestamp
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
+    pub timestamp: Option<String>,
+}
+
+impl_into_result!(JackStatus, timestamp: timestamp);
+
+impl JackStatus {
+    /// Interpret the peripheral data as UTF-8 text
+    pub fn peripheral_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.peripheral_data)
+    }
+
+    /// Interpret the peripheral data as JSON, the usual way integrators
+    /// pack structured data into it
+    ///
+    /// ```
+    /// use seersdk_rs::JackStatus;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Shelf {
+    ///     id: u32,
+    /// }
+    ///
+    /// let status: JackStatus = serde_json::from_str(r#"{
+    ///     "jack_emc": false, "jack_enable": true, "jack_error_code": 0,
+    ///     "jack_height": 0.05, "jack_isFull": true, "jack_mode": true,
+    ///     "jack_speed": 0, "jack_state": 0,
+    ///     "peripheral_data": [123, 34, 105, 100, 34, 58, 55, 125]
+    /// }"#)
+    /// .unwrap();
+    /// let shelf: Shelf = status.peripheral_as().unwrap();
+    /// assert_eq!(shelf.id, 7);
+    /// ```
+    pub fn peripheral_as<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json::from_slice(&self.peripheral_data)
+    }
+}
+
+/// Status of the fork
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ForkStatus {
+    /// Current fork height in meters
+    pub fork_height: f64,
+    /// Whether the fork reached the requested height
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub fork_height_in_place: bool,
+    /// Current mode is automatic or manual
+    #[serde(
+        rename = "fork_auto_flag",
+        default,
+        deserialize_with = "bool_or_int"
+    )]
+    pub automatic_mode: bool,
+    /// Forward (reach) travel in meters
+    #[serde(rename = "forward_val", default)]
+    pub forward: f64,
+    /// Whether the forward travel reached its target
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub forward_in_place: bool,
+    /// Measured pressure, `None` on forks without a sensor
+    #[serde(rename = "fork_pressure_actual", default)]
+    pub pressure: Option<f64>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
+    pub timestamp: Option<String>,
+}
+
+impl_into_result!(ForkStatus, timestamp: timestamp);
+
+/// A digital input or output
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DigitalIo {
+    pub id: u32,
+    /// Where the signal comes from, e.g. `"normal"` or `"virtual"`
+    #[serde(default)]
+    pub source: String,
+    /// Whether the signal is high
+    #[serde(deserialize_with = "bool_or_int")]
+    pub status: bool,
+    /// Whether the port is enabled
+    #[serde(default = "default_true", deserialize_with = "bool_or_int")]
+    pub valid: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Combined pose, battery, block and container status (1100)
+///
+/// The robot reports everything in one flat object, sections missing from
+/// it are `None`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AllStatus1 {
+    #[serde(flatten)]
+    pub pose: Option<RobotPose>,
+    #[serde(flatten)]
+    pub battery: Option<BatteryStatus>,
+    #[serde(flatten)]
+    pub block: Option<BlockStatus>,
+    /// Goods bound to the robot's containers
+    #[serde(default)]
+    pub containers: Vec<ContainerGoods>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
+    pub timestamp: Option<String>,
+}
+
+impl_into_result!(AllStatus1, timestamp: timestamp);
+
+/// Goods bound to one of the robot's containers
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ContainerGoods {
+    #[serde(rename = "container_name")]
+    pub container: String,
+    /// Empty if the container holds no goods
+    #[serde(rename = "goods_id", default)]
+    pub goods: String,
+}
+
+/// Combined speed, navigation and area status (1101)
+///
+/// The robot reports everything in one flat object, sections missing from
+/// it are `None`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AllStatus2 {
+    #[serde(flatten, deserialize_with = "speed_section")]
+    pub speed: Option<RobotSpeed>,
+    #[serde(flatten)]
+    pub nav: Option<NavStatus>,
+    #[serde(flatten, deserialize_with = "area_section")]
+    pub area: Option<RobotArea>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
+    pub timestamp: Option<String>,
+}
+
+impl_into_result!(AllStatus2, timestamp: timestamp);
+
+/// Combined peripheral status (1102)
+///
+/// The robot reports everything in one flat object, the sections of
+/// peripherals the robot doesn't have are `None`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AllStatus3 {
+    #[serde(flatten)]
+    pub jack: Option<JackStatus>,
+    #[serde(flatten)]
+    pub fork: Option<ForkStatus>,
+    /// Digital inputs
+    #[serde(rename = "DI", default)]
+    pub inputs: Vec<DigitalIo>,
+    /// Digital outputs
+    #[serde(rename = "DO", default)]
+    pub outputs: Vec<DigitalIo>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+    #[serde(
+        rename = "create_on",
+        default,
+        deserialize_with = "timestamp_string"
+    )]
+    pub timestamp: Option<String>,
+}
+
+impl_into_result!(AllStatus3, timestamp: timestamp);
+
+/// Navigation status of the robot
+///
+/// Only `task_status` is always present, the robot omits the remaining
+/// fields e.g. when queried with
+/// [`GetNavStatus::with_simple`](crate::api::GetNavStatus::with_simple).
+/// Some firmware names it `state`, which is accepted as well.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NavStatus {
+    #[serde(rename = "task_status", alias = "state")]
+    pub status: TaskStatus,
+    #[serde(rename = "task_type", default)]
+    pub ty: TaskType,
+    #[serde(default)]
+    pub target_id: PointId,
+    /// Target point coordinates (x, y, angle)
+    #[serde(default)]
+    pub target_point: [f64; 3],
 
     /// Stations already passed on the current navigation path,
     /// an array of stations, this field is only valid when task_type is 3.
     /// All intermediate points already passed will be listed here
+    #[serde(default)]
     pub finished_path: Vec<PointId>,
 
     /// Stations on the current navigation path that have not yet been passed,
     /// represented as an array of stations, are only valid when task_type is 3.
     /// All intermediate points that have not yet been passed will be listed here.
+    #[serde(default)]
     pub unfinished_path: Vec<PointId>,
 
     /// Navigation Task Additional Information
+    #[serde(default)]
     pub move_status_info: String,
 
     /// API Error Code
     #[serde(rename = "ret_code", default)]
     pub code: Option<StatusCode>,
     /// API Upload Timestamp
+    #[serde(default, deserialize_with = "timestamp_string")]
     pub create_on: Option<String>,
     /// Error Message
     #[serde(rename = "err_msg", default)]
     pub message: String,
 }
 
+impl_into_result!(NavStatus, timestamp: create_on);
+
+impl NavStatus {
+    /// Number of stations passed and still to pass on the current path,
+    /// e.g. to show "3/7 stations"
+    ///
+    /// Counted from `finished_path` and `unfinished_path`, so only
+    /// meaningful for path navigation, see [`NavStatus::is_path_nav`].
+    pub fn progress(&self) -> (usize, usize) {
+        (self.finished_path.len(), self.unfinished_path.len())
+    }
+
+    /// Whether the robot navigates along a path to a station
+    pub fn is_path_nav(&self) -> bool {
+        self.ty == TaskType::PathNavToSite
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
 #[repr(u32)]
 pub enum TaskType {
@@ -359,12 +1333,22 @@ pub enum TaskType {
     Other = 100,
 }
 
+// Unknown values fall back to `Other`, a missing field means no navigation
+#[allow(clippy::derivable_impls)]
+impl Default for TaskType {
+    fn default() -> Self {
+        TaskType::NoNav
+    }
+}
+
 impl_serde_for_num_enum!(TaskType);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::FromPrimitive)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, num_enum::FromPrimitive,
+)]
 #[repr(u32)]
 pub enum TaskStatus {
-    #[num_enum(default)]
+    #[default]
     None = 0,
     Waiting = 1,
     Running = 2,
@@ -378,44 +1362,404 @@ pub enum TaskStatus {
 
 impl_serde_for_num_enum!(TaskStatus);
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+impl TaskStatus {
+    /// Whether the task has finished and will not change state anymore
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Completed
+                | TaskStatus::Failed
+                | TaskStatus::Canceled
+                | TaskStatus::OverTime
+                | TaskStatus::NotFound
+        )
+    }
+
+    /// Whether the task is still in progress, including waiting or paused
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Waiting | TaskStatus::Running | TaskStatus::Suspended
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TaskStatusItem {
     pub task_id: TaskId,
     pub status: TaskStatus,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TaskPackage {
     /// The station closest to the robot within a certain linear distance (this distance is a
+    #[serde(default)]
     pub closest_target: PointId,
     /// The "source_id" in the navigation task currently being executed by the robot
+    #[serde(default)]
     pub source_name: TaskId,
     /// The "id" of the navigation task currently being executed by the robot
+    #[serde(default)]
     pub target_name: TaskId,
     /// In the navigation task currently being executed by the robot, for the corresponding path,
-    /// the proportion of the part that the robot has completed to the entire path
+    /// the proportion of the part that the robot has completed to the entire path (0.0 to 1.0)
+    #[serde(default)]
     pub percentage: f64,
     /// Projection distance of the robot to the "path corresponding to the currently executing
     /// navigation task
+    #[serde(default)]
     pub distance: Option<f64>,
 
-    #[serde(rename = "task_status_list")]
+    #[serde(rename = "task_status_list", default)]
     pub tasks: Vec<TaskStatusItem>,
     /// During the navigation process, some prompts from the robot to the user can be output to the
     /// front end. This field does not participate in actual logical judgment
+    #[serde(default)]
     pub info: String,
 
     #[serde(rename = "ret_code", default)]
     pub code: Option<StatusCode>,
     #[serde(rename = "err_msg", default)]
     pub message: String,
+    #[serde(default, deserialize_with = "timestamp_string")]
     pub create_on: Option<String>,
 }
 
+impl TaskPackage {
+    /// Progress of the current path in percent (0.0 to 100.0)
+    pub fn percent_complete(&self) -> f64 {
+        (self.percentage * 100.0).clamp(0.0, 100.0)
+    }
+
+    /// Whether every reported task reached a terminal state
+    ///
+    /// Returns `false` when no task is reported at all.
+    pub fn is_done(&self) -> bool {
+        !self.tasks.is_empty()
+            && self.tasks.iter().all(|task| task.status.is_terminal())
+    }
+}
+
+impl_into_result!(TaskPackage, timestamp: create_on);
+
+/// Register values read from an attached PLC
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ModbusData {
+    #[serde(default)]
+    pub values: Vec<u16>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(ModbusData);
+
+/// Robot model file, the robot's description used e.g. for collision
+/// visualization, as stored on the robot
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelFile {
+    pub data: Vec<u8>,
+}
+
+impl ModelFile {
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self { data: data.into() }
+    }
+
+    /// Parse the file, which is usually JSON, into a [`RobotModel`]
+    pub fn parse(&self) -> Result<RobotModel, serde_json::Error> {
+        serde_json::from_slice(&self.data)
+    }
+}
+
+/// Parsed robot model file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RobotModel {
+    /// Outline of the robot as `[x, y]` points in meters, relative to the
+    /// robot center
+    #[serde(default)]
+    pub contour: Vec<[f64; 2]>,
+    /// Every other field of the model file
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Status of the robotic arm
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ArmStatus {
+    /// Joint positions in radians
+    #[serde(default)]
+    pub joints: Vec<f64>,
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub moving: bool,
+    /// Arm controller error, 0 if none
+    #[serde(default)]
+    pub error_code: u32,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(ArmStatus);
+
+/// 3D QR tag detected during mapping
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tag3d {
+    pub id: String,
+    /// Position in meters
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Detection confidence (0.0 to 1.0)
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+/// 3D QR tags currently detected
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tag3dData {
+    #[serde(default)]
+    pub tags: Vec<Tag3d>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(Tag3dData);
+
+/// Planned navigation path
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NavPath {
+    /// Points along the path as `[x, y, angle]`, meters and radians
+    #[serde(default)]
+    pub points: Vec<[f64; 3]>,
+    /// Length of the path in meters
+    #[serde(default)]
+    pub total_distance: f64,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(NavPath);
+
+/// Audio currently played by the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SoundStatus {
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub playing: bool,
+    /// Name of the audio file being played
+    #[serde(default)]
+    pub current: Option<String>,
+    /// Volume in range 0.0 to 1.0
+    #[serde(default)]
+    pub volume: f64,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(SoundStatus);
+
+/// Names of the scripts stored on the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScriptList {
+    #[serde(default)]
+    pub scripts: Vec<String>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(ScriptList);
+
+/// Source code of a script stored on the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScriptContent {
+    #[serde(default)]
+    pub content: String,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(ScriptContent);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, num_enum::FromPrimitive,
+)]
+#[repr(u32)]
+pub enum CalibrationState {
+    #[default]
+    Idle = 0,
+    Running = 1,
+    Finished = 2,
+    Failed = 3,
+}
+
+impl_serde_for_num_enum!(CalibrationState);
+
+/// Progress of the running calibration
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CalibrationStatus {
+    #[serde(default)]
+    pub state: CalibrationState,
+    /// Kind of the running or last calibration
+    #[serde(default)]
+    pub calib_type: Option<u32>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(CalibrationStatus);
+
+/// Outcome of the last calibration
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CalibrationResult {
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub success: bool,
+    /// Calibrated values, their layout depends on the calibration type
+    #[serde(default)]
+    pub data: serde_json::Value,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(CalibrationResult);
+
+/// Connection and fix quality of the GNSS receiver
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GnssStatus {
+    #[serde(default, deserialize_with = "bool_or_int")]
+    pub connected: bool,
+    /// Fix type reported by the receiver, 0 means no fix
+    #[serde(default)]
+    pub fix_type: u32,
+    /// Number of satellites in use
+    #[serde(default)]
+    pub satellites: u32,
+    /// Latitude in degrees
+    #[serde(default)]
+    pub lat: Option<f64>,
+    /// Longitude in degrees
+    #[serde(default)]
+    pub lon: Option<f64>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(GnssStatus);
+
+/// GNSS devices attached to the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GnssList {
+    #[serde(default)]
+    pub devices: Vec<String>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(GnssList);
+
+/// Files stored on the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FileList {
+    #[serde(default)]
+    pub files: Vec<FileEntry>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(FileList);
+
+/// A single file of a [`FileList`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FileEntry {
+    pub name: String,
+    /// Size in bytes
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Names of the task chains stored on the robot
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TaskListNames {
+    #[serde(rename = "tasklists", alias = "lists", default)]
+    pub lists: Vec<String>,
+
+    #[serde(rename = "ret_code", default)]
+    pub code: Option<StatusCode>,
+    #[serde(rename = "err_msg", default)]
+    pub message: String,
+}
+
+impl_into_result!(TaskListNames);
+
 #[cfg(test)]
 mod tests {
     use crate::StatusCode;
 
+    #[test]
+    fn test_nested_status_header() {
+        use super::{GnssList, StatusMessage, flatten_status_header};
+
+        let parse = |body: &str| -> StatusMessage {
+            let mut value = serde_json::from_str(body).unwrap();
+            flatten_status_header(&mut value);
+            serde_json::from_value(value).unwrap()
+        };
+        let flat = parse(r#"{"ret_code": 40001, "err_msg": "busy"}"#);
+        let nested = parse(
+            r#"{"header": {"ret_code": 40001, "err_msg": "busy"}, "data": {}}"#,
+        );
+        assert_eq!(flat.code, StatusCode::ParamMissing);
+        assert_eq!(nested, flat);
+
+        // Payload fields are taken from `data`
+        let mut value = serde_json::json!({
+            "header": {"ret_code": 0, "err_msg": ""},
+            "data": {"devices": ["gnss0"]}
+        });
+        assert!(flatten_status_header(&mut value));
+        let list: GnssList = serde_json::from_value(value).unwrap();
+        assert_eq!(list.devices, ["gnss0"]);
+        assert_eq!(list.code, Some(StatusCode::Success));
+
+        // A header without status fields is payload, not an envelope
+        let mut value = serde_json::json!({"header": {"seq": 1}});
+        assert!(!flatten_status_header(&mut value));
+        assert_eq!(value, serde_json::json!({"header": {"seq": 1}}));
+    }
+
     #[test]
     fn test_error_code_serialization() {
         use serde::{Deserialize, Serialize};
@@ -442,6 +1786,79 @@ mod tests {
         assert_eq!(deserialized_custom.code, StatusCode::Custom);
     }
 
+    #[test]
+    fn test_has_status() {
+        use crate::{
+            BlockStatus, CommonInfo, HasStatus, RobotPose, StatusMessage,
+        };
+
+        let pose: RobotPose = serde_json::from_str(
+            r#"{"x": 0.0, "y": 0.0, "angle": 0.0, "confidence": 1.0}"#,
+        )
+        .unwrap();
+        assert_eq!(pose.status_code(), None);
+        assert!(pose.ok());
+
+        let info: CommonInfo = serde_json::from_str(
+            r#"{"id": "r1", "version": "1.0", "model": "m", "ret_code": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(info.status_code(), Some(StatusCode::Success));
+        assert!(info.ok());
+
+        let block: BlockStatus =
+            serde_json::from_str(r#"{"blocked": false, "ret_code": 40001}"#)
+                .unwrap();
+        assert_eq!(block.status_code(), Some(StatusCode::ParamMissing));
+        assert!(!block.ok());
+
+        let status: StatusMessage =
+            serde_json::from_str(r#"{"ret_code": 40000}"#).unwrap();
+        assert!(!status.ok());
+
+        // Usable generically
+        fn all_ok(responses: &[&dyn HasStatus]) -> bool {
+            responses.iter().all(|response| response.ok())
+        }
+        assert!(all_ok(&[&pose, &info]));
+        assert!(!all_ok(&[&pose, &block]));
+    }
+
+    #[test]
+    fn test_num_enum_from_numeric_string() {
+        use crate::{JackOperationStatus, TaskStatus, TaskType};
+
+        #[derive(serde::Deserialize)]
+        struct TestStruct {
+            code: StatusCode,
+        }
+
+        let from_string: TestStruct =
+            serde_json::from_str(r#"{"code": "40001"}"#).unwrap();
+        let from_number: TestStruct =
+            serde_json::from_str(r#"{"code": 40001}"#).unwrap();
+        assert_eq!(from_string.code, StatusCode::ParamMissing);
+        assert_eq!(from_number.code, StatusCode::ParamMissing);
+
+        assert_eq!(
+            serde_json::from_str::<TaskStatus>(r#""4""#).unwrap(),
+            TaskStatus::Completed
+        );
+        assert_eq!(
+            serde_json::from_str::<TaskType>(r#""3""#).unwrap(),
+            serde_json::from_str::<TaskType>("3").unwrap()
+        );
+        assert_eq!(
+            serde_json::from_str::<JackOperationStatus>(r#""1""#).unwrap(),
+            serde_json::from_str::<JackOperationStatus>("1").unwrap()
+        );
+
+        assert!(
+            serde_json::from_str::<TestStruct>(r#"{"code": "ok"}"#).is_err()
+        );
+        assert!(serde_json::from_str::<TestStruct>(r#"{"code": -1}"#).is_err());
+    }
+
     #[test]
     #[allow(clippy::approx_constant)]
     fn test_robot_pose_serialization_deserializatio() {
@@ -516,4 +1933,523 @@ mod tests {
         assert_eq!(status_no_code.code, StatusCode::Success);
         assert_eq!(status_no_code.message, "");
     }
+
+    #[test]
+    fn test_status_message_into_result() {
+        let ok: super::StatusMessage =
+            serde_json::from_str(r#"{"ret_code": 0}"#).unwrap();
+        assert!(ok.into_result().is_ok());
+
+        let no_code: super::StatusMessage =
+            serde_json::from_str(r#"{"err_msg": ""}"#).unwrap();
+        assert_eq!(no_code.code, StatusCode::Success);
+        assert!(<()>::try_from(no_code).is_ok());
+
+        let failed: super::StatusMessage = serde_json::from_str(
+            r#"{"ret_code": 40102, "err_msg": "busy", "create_on": "now"}"#,
+        )
+        .unwrap();
+        match failed.into_result() {
+            Err(crate::RbkError::BadResponse {
+                code,
+                message,
+                timestamp,
+            }) => {
+                assert_eq!(code, StatusCode::RobotBusy);
+                assert_eq!(message, "busy");
+                assert_eq!(timestamp.as_deref(), Some("now"));
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_response_into_result() {
+        let pose: super::RobotPose = serde_json::from_str(
+            r#"{"x": 1.0, "y": 2.0, "angle": 0.0, "confidence": 0.9}"#,
+        )
+        .unwrap();
+        let pose = pose.into_result().unwrap();
+        assert_eq!(pose.x, 1.0);
+
+        let blocked: super::BlockStatus = serde_json::from_str(
+            r#"{"blocked": false, "ret_code": 40001, "err_msg": "missing"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            blocked.into_result(),
+            Err(crate::RbkError::BadResponse {
+                code: StatusCode::ParamMissing,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_nav_status_deserialization() {
+        // Body emitted by the mock server for API 1020
+        let mock_body = r#"{"create_on":"1700000000","err_msg":"","finished_path":["a"],"move_status_info":"Mock navigation running","ret_code":0,"target_id":"b","target_point":[1.0,2.0,0.0],"task_status":2,"task_type":3,"unfinished_path":["c"]}"#;
+
+        let status: super::NavStatus = serde_json::from_str(mock_body).unwrap();
+        assert_eq!(status.status, super::TaskStatus::Running);
+        assert_eq!(status.ty, super::TaskType::PathNavToSite);
+        assert_eq!(status.target_id, "b");
+        assert_eq!(status.target_point, [1.0, 2.0, 0.0]);
+        assert_eq!(status.finished_path, vec!["a".to_string()]);
+        assert_eq!(status.unfinished_path, vec!["c".to_string()]);
+        assert_eq!(status.move_status_info, "Mock navigation running");
+        assert_eq!(status.create_on.as_deref(), Some("1700000000"));
+
+        let simple = r#"{"task_status": 4, "ret_code": 0}"#;
+        let status: super::NavStatus = serde_json::from_str(simple).unwrap();
+        assert_eq!(status.status, super::TaskStatus::Completed);
+        assert_eq!(status.ty, super::TaskType::NoNav);
+        assert!(status.target_id.is_empty());
+        assert!(status.finished_path.is_empty());
+        assert!(status.unfinished_path.is_empty());
+        assert!(status.move_status_info.is_empty());
+        assert_eq!(status.create_on, None);
+
+        let state = r#"{"state": 5, "task_type": 1}"#;
+        let status: super::NavStatus = serde_json::from_str(state).unwrap();
+        assert_eq!(status.status, super::TaskStatus::Failed);
+        assert_eq!(status.ty, super::TaskType::FreeNavToPoint);
+
+        let numeric = r#"{"task_status": 2, "create_on": 1700000000}"#;
+        let status: super::NavStatus = serde_json::from_str(numeric).unwrap();
+        assert_eq!(status.create_on.as_deref(), Some("1700000000"));
+    }
+
+    #[test]
+    fn test_nav_status_progress() {
+        // Mock 1020 body halfway through a designed path
+        let mock_body = r#"{"task_status":2,"task_type":3,"target_id":"LM4","target_point":[4.0,0.0,0.0],"finished_path":["LM1","LM2","LM3"],"unfinished_path":["LM5","LM6","LM7","LM8"],"move_status_info":"Mock navigation running","ret_code":0,"err_msg":""}"#;
+
+        let status: super::NavStatus = serde_json::from_str(mock_body).unwrap();
+        assert!(status.is_path_nav());
+        assert_eq!(status.progress(), (3, 4));
+
+        let free_nav = r#"{"task_status":2,"task_type":1}"#;
+        let status: super::NavStatus = serde_json::from_str(free_nav).unwrap();
+        assert!(!status.is_path_nav());
+        assert_eq!(status.progress(), (0, 0));
+    }
+
+    #[test]
+    fn test_task_status_classification() {
+        use crate::TaskStatus;
+
+        let cases = [
+            (TaskStatus::None, false, false),
+            (TaskStatus::Waiting, false, true),
+            (TaskStatus::Running, false, true),
+            (TaskStatus::Suspended, false, true),
+            (TaskStatus::Completed, true, false),
+            (TaskStatus::Failed, true, false),
+            (TaskStatus::Canceled, true, false),
+            (TaskStatus::OverTime, true, false),
+            (TaskStatus::NotFound, true, false),
+        ];
+
+        for (status, terminal, active) in cases {
+            assert_eq!(status.is_terminal(), terminal, "{:?}", status);
+            assert_eq!(status.is_active(), active, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn test_response_equality() {
+        use crate::{RobotPose, StatusCode};
+
+        let pose: RobotPose = serde_json::from_str(
+            r#"{"x": 1.5, "y": -2.0, "angle": 0.5, "confidence": 0.9, "ret_code": 0}"#,
+        )
+        .unwrap();
+
+        let expected = RobotPose {
+            x: 1.5,
+            y: -2.0,
+            angle: 0.5,
+            confidence: 0.9,
+            code: Some(StatusCode::Success),
+            message: String::new(),
+        };
+        assert_eq!(pose, expected);
+        assert_ne!(pose, RobotPose { x: 0.0, ..expected });
+    }
+
+    #[test]
+    fn test_operation_info_without_controller_metrics() {
+        use crate::OperationInfo;
+
+        let info: OperationInfo = serde_json::from_str(
+            r#"{"odo": 12.5, "total": 3600000, "total_time": 7200000}"#,
+        )
+        .unwrap();
+
+        assert_eq!(info.mileage, 12.5);
+        assert_eq!(info.controller_temp, None);
+        assert_eq!(info.controller_humi, None);
+        assert_eq!(info.controller_voltage, None);
+
+        let info: OperationInfo = serde_json::from_str(
+            r#"{"odo": 0, "total": 0, "total_time": 0, "controller_temp": 41.5}"#,
+        )
+        .unwrap();
+        assert_eq!(info.controller_temp, Some(41.5));
+    }
+
+    #[test]
+    fn test_operation_info_durations() {
+        use crate::OperationInfo;
+        use std::time::Duration;
+
+        let info: OperationInfo = serde_json::from_str(
+            r#"{"odo": 12.5, "total": 90500, "total_time": 7200000}"#,
+        )
+        .unwrap();
+
+        assert_eq!(info.session_duration(), Duration::from_millis(90500));
+        assert_eq!(info.total_duration(), Duration::from_secs(7200));
+        assert_eq!(info.odometry_meters(), 12.5);
+    }
+
+    #[test]
+    fn test_task_package_deserialization() {
+        use crate::{TaskPackage, TaskStatus};
+
+        // Body as produced by the mock server
+        let mock_body = r#"{"closest_target":"LM2","source_name":"SELF_POSITION","target_name":"LM2","percentage":0.25,"distance":3.75,"task_status_list":[{"task_id":"task_1","status":4},{"task_id":"task_2","status":2}],"info":"Navigation in progress","ret_code":0,"err_msg":"","create_on":"1700000000"}"#;
+
+        let package: TaskPackage = serde_json::from_str(mock_body).unwrap();
+        assert_eq!(package.percentage, 0.25);
+        assert_eq!(package.percent_complete(), 25.0);
+        assert_eq!(package.distance, Some(3.75));
+        assert_eq!(package.tasks.len(), 2);
+        assert_eq!(package.tasks[1].status, TaskStatus::Running);
+        assert!(!package.is_done());
+        assert_eq!(package.create_on.as_deref(), Some("1700000000"));
+
+        // Only the status code is guaranteed
+        let package: TaskPackage =
+            serde_json::from_str(r#"{"ret_code":0}"#).unwrap();
+        assert_eq!(package.percent_complete(), 0.0);
+        assert!(package.tasks.is_empty());
+        assert!(!package.is_done());
+
+        let package: TaskPackage =
+            serde_json::from_str(r#"{"ret_code":0,"create_on":1700000000}"#)
+                .unwrap();
+        assert_eq!(package.create_on.as_deref(), Some("1700000000"));
+    }
+
+    #[test]
+    fn test_jack_peripheral_data() {
+        use super::JackStatus;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Shelf {
+            id: String,
+            weight_kg: f64,
+        }
+
+        let shelf = Shelf {
+            id: "S-12".into(),
+            weight_kg: 80.5,
+        };
+        let bytes = serde_json::to_vec(&shelf).unwrap();
+        let body = serde_json::json!({
+            "jack_emc": false,
+            "jack_enable": true,
+            "jack_error_code": 0,
+            "jack_height": 0.05,
+            "jack_isFull": true,
+            "jack_mode": true,
+            "jack_speed": 0,
+            "jack_state": 0,
+            "peripheral_data": bytes,
+        });
+        let status: JackStatus = serde_json::from_value(body).unwrap();
+        assert_eq!(status.peripheral_as::<Shelf>().unwrap(), shelf);
+
+        // Some firmware sends the data as a plain string
+        let body = r#"{
+            "jack_emc": false,
+            "jack_enable": true,
+            "jack_error_code": 0,
+            "jack_height": 0,
+            "jack_isFull": false,
+            "jack_mode": true,
+            "jack_speed": 0,
+            "jack_state": 0,
+            "peripheral_data": "rack-3"
+        }"#;
+        let status: JackStatus = serde_json::from_str(body).unwrap();
+        assert_eq!(status.peripheral_str().unwrap(), "rack-3");
+        assert!(status.peripheral_as::<Shelf>().is_err());
+    }
+
+    #[test]
+    fn test_all_status1_deserialization() {
+        use super::{AllStatus1, BlockReason, ContainerGoods};
+
+        let body = r#"{
+            "x": 1.5,
+            "y": -2.0,
+            "angle": 0.5,
+            "confidence": 0.9,
+            "battery_level": 0.8,
+            "battery_temp": 25.0,
+            "charging": 0,
+            "voltage": 48.0,
+            "current": 1.2,
+            "blocked": true,
+            "block_reason": 1,
+            "containers": [{"container_name": "c1", "goods_id": "pallet_1"}],
+            "ret_code": 0,
+            "err_msg": "",
+            "create_on": 1700000000
+        }"#;
+
+        let status: AllStatus1 = serde_json::from_str(body).unwrap();
+        let pose = status.pose.as_ref().unwrap();
+        assert_eq!((pose.x, pose.y, pose.angle), (1.5, -2.0, 0.5));
+        assert_eq!(status.battery.as_ref().unwrap().battery_level, 0.8);
+        let block = status.block.as_ref().unwrap();
+        assert!(block.is_blocked);
+        assert_eq!(block.reasons, [BlockReason::Laser]);
+        assert_eq!(
+            status.containers,
+            [ContainerGoods {
+                container: "c1".to_string(),
+                goods: "pallet_1".to_string(),
+            }]
+        );
+        assert_eq!(status.code, Some(StatusCode::Success));
+        assert_eq!(status.timestamp.as_deref(), Some("1700000000"));
+
+        // Sections missing from the body are left out
+        let status: AllStatus1 =
+            serde_json::from_str(r#"{"battery_level": 0.5}"#).unwrap();
+        assert_eq!(status.pose, None);
+        assert_eq!(status.battery, None);
+        assert_eq!(status.block, None);
+    }
+
+    #[test]
+    fn test_all_status2_deserialization() {
+        use super::{AllStatus2, TaskStatus, TaskType};
+
+        let body = r#"{
+            "vx": 0.5,
+            "vy": 0.0,
+            "w": 0.1,
+            "task_status": 2,
+            "task_type": 3,
+            "target_id": "LM2",
+            "finished_path": ["LM1"],
+            "unfinished_path": ["LM3"],
+            "area_ids": ["area_a"],
+            "max_speed": 0.3,
+            "ret_code": 0,
+            "create_on": "1700000000"
+        }"#;
+
+        let status: AllStatus2 = serde_json::from_str(body).unwrap();
+        assert_eq!(status.speed.as_ref().unwrap().vx, 0.5);
+        let nav = status.nav.as_ref().unwrap();
+        assert_eq!(nav.status, TaskStatus::Running);
+        assert_eq!(nav.ty, TaskType::PathNavToSite);
+        assert_eq!(nav.target_id, "LM2");
+        assert_eq!(nav.finished_path, ["LM1"]);
+        let area = status.area.as_ref().unwrap();
+        assert_eq!(area.area_ids, ["area_a"]);
+        assert_eq!(area.max_speed, Some(0.3));
+        assert_eq!(status.timestamp.as_deref(), Some("1700000000"));
+    }
+
+    #[test]
+    fn test_all_status2_missing_sections() {
+        use super::AllStatus2;
+
+        let body = r#"{"task_status": 2, "ret_code": 0}"#;
+
+        let status: AllStatus2 = serde_json::from_str(body).unwrap();
+        assert!(status.nav.is_some());
+        assert_eq!(status.speed, None);
+        assert_eq!(status.area, None);
+
+        let body = r#"{"w": 0.2, "area_ids": [], "ret_code": 0}"#;
+
+        let status: AllStatus2 = serde_json::from_str(body).unwrap();
+        assert_eq!(status.speed.as_ref().unwrap().vx, 0.0);
+        assert_eq!(status.speed.as_ref().unwrap().w, 0.2);
+        assert!(status.area.as_ref().unwrap().area_ids.is_empty());
+    }
+
+    #[test]
+    fn test_all_status3_deserialization() {
+        use super::{AllStatus3, JackOperationStatus};
+
+        let body = r#"{
+            "jack_emc": false,
+            "jack_enable": true,
+            "jack_error_code": 0,
+            "jack_height": 0.2,
+            "jack_isFull": true,
+            "jack_mode": true,
+            "jack_speed": 0,
+            "jack_state": 4,
+            "peripheral_data": [],
+            "fork_height": 0.35,
+            "fork_height_in_place": 1,
+            "fork_auto_flag": true,
+            "forward_val": 0.0,
+            "forward_in_place": true,
+            "DI": [{"id": 0, "source": "normal", "status": true, "valid": true}],
+            "DO": [{"id": 3, "status": 0}],
+            "ret_code": 0,
+            "create_on": "1700000000"
+        }"#;
+
+        let status: AllStatus3 = serde_json::from_str(body).unwrap();
+        let jack = status.jack.as_ref().unwrap();
+        assert!(jack.has_payload);
+        assert_eq!(jack.operation, JackOperationStatus::from(4));
+        let fork = status.fork.as_ref().unwrap();
+        assert_eq!(fork.fork_height, 0.35);
+        assert!(fork.fork_height_in_place);
+        assert_eq!(fork.pressure, None);
+        assert!(status.inputs[0].status);
+        assert_eq!(status.outputs[0].id, 3);
+        assert!(!status.outputs[0].status);
+        assert!(status.outputs[0].valid);
+        assert_eq!(status.timestamp.as_deref(), Some("1700000000"));
+
+        // A robot without a fork leaves that section out
+        let status: AllStatus3 = serde_json::from_str(
+            r#"{"jack_emc": 0, "jack_enable": 1, "jack_error_code": 0,
+                "jack_height": 0, "jack_isFull": 0, "jack_mode": 1,
+                "jack_speed": 0, "jack_state": 0, "ret_code": 0}"#,
+        )
+        .unwrap();
+        assert!(status.jack.is_some());
+        assert_eq!(status.fork, None);
+        assert!(status.inputs.is_empty());
+    }
+
+    #[test]
+    fn test_block_reasons() {
+        use super::{BlockReason, BlockStatus};
+
+        let status: BlockStatus = serde_json::from_str(
+            r#"{"blocked": true, "block_reason": 2, "ret_code": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(status.reason, Some(BlockReason::Fallingdown));
+        assert_eq!(status.reasons, vec![BlockReason::Fallingdown]);
+
+        let status: BlockStatus = serde_json::from_str(
+            r#"{"blocked": true, "block_reasons": [1, 3], "ret_code": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(status.reason, Some(BlockReason::Laser));
+        assert_eq!(
+            status.reasons,
+            vec![BlockReason::Laser, BlockReason::Collision]
+        );
+
+        let status: BlockStatus = serde_json::from_str(
+            r#"{"blocked": true, "block_reason": [4, 1], "ret_code": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(status.reason, Some(BlockReason::Infrared));
+        assert_eq!(status.reasons.len(), 2);
+
+        let status: BlockStatus =
+            serde_json::from_str(r#"{"blocked": false, "ret_code": 0}"#)
+                .unwrap();
+        assert_eq!(status.reason, None);
+        assert!(status.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_bool_as_int() {
+        use super::{BlockStatus, JackStatus};
+
+        let as_bool: BlockStatus =
+            serde_json::from_str(r#"{"blocked": true, "ret_code": 0}"#)
+                .unwrap();
+        let as_int: BlockStatus =
+            serde_json::from_str(r#"{"blocked": 1, "ret_code": 0}"#).unwrap();
+        assert!(as_bool.is_blocked);
+        assert_eq!(as_bool, as_int);
+
+        let jack = r#"{
+            "jack_emc": 0,
+            "jack_enable": 1,
+            "jack_error_code": 0,
+            "jack_height": 0.0,
+            "jack_isFull": 1,
+            "jack_mode": false,
+            "jack_speed": 0,
+            "jack_state": 0,
+            "peripheral_data": []
+        }"#;
+        let jack: JackStatus = serde_json::from_str(jack).unwrap();
+        assert!(!jack.emergency_stop);
+        assert!(jack.enabled);
+        assert!(jack.has_payload);
+        assert!(!jack.automatic_mode);
+
+        let invalid = serde_json::from_str::<BlockStatus>(
+            r#"{"blocked": 2, "ret_code": 0}"#,
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_status_message_created_at() {
+        use crate::StatusMessage;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let status: StatusMessage = serde_json::from_str(
+            r#"{"ret_code": 0, "err_msg": "", "create_on": "1700000000"}"#,
+        )
+        .unwrap();
+        assert_eq!(status.timestamp.as_deref(), Some("1700000000"));
+        assert_eq!(
+            status.created_at(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+
+        let status: StatusMessage = serde_json::from_str(
+            r#"{"ret_code": 0, "create_on": 1700000000.5}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            status.created_at(),
+            Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_500))
+        );
+
+        let status: StatusMessage =
+            serde_json::from_str(r#"{"ret_code": 0, "create_on": "now"}"#)
+                .unwrap();
+        assert_eq!(status.created_at(), None);
+
+        // Out of range for a `Duration`
+        for create_on in ["1e30", "-5", "NaN", "inf"] {
+            let status: StatusMessage = serde_json::from_str(&format!(
+                r#"{{"ret_code": 0, "create_on": "{}"}}"#,
+                create_on
+            ))
+            .unwrap();
+            assert_eq!(status.created_at(), None, "{}", create_on);
+        }
+
+        let status: StatusMessage =
+            serde_json::from_str(r#"{"ret_code": 0}"#).unwrap();
+        assert_eq!(status.timestamp, None);
+        assert_eq!(status.created_at(), None);
+    }
 }
@@ -0,0 +1,323 @@
+use crate::api::{
+    BatteryStatus, DynRequest, FromRawResponseBody, FromResponseBody,
+    MoveToTarget, PushConfig, TaskStatus, ToRawRequestBody, ToRequestBody,
+};
+use crate::client::{
+    ConfirmResult, ConnectionEvent, Diagnostics, DiagnosticsBundle, RbkClient,
+    ResponseMeta, RobotSnapshot, TransportJob, TransportResult,
+};
+use crate::error::RbkResult;
+use crate::frame::RbkFrame;
+use std::collections::HashMap;
+use std::future::Future;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::{broadcast, oneshot};
+
+/// Synchronous wrapper around [`RbkClient`]
+///
+/// Intended for callers without a tokio runtime of their own, such as a
+/// synchronous control loop or a GUI event handler. Creating the client
+/// spins up a dedicated thread running a current-thread tokio runtime,
+/// which drives the connections in the background. Every method blocks the
+/// calling thread until the underlying async call completes.
+///
+/// Must not be used from within an async context, calls would block the
+/// executor.
+///
+/// # Example
+///
+/// ```no_run
+/// use seersdk_rs::{BatteryStatusRequest, BlockingRbkClient};
+/// use std::time::Duration;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BlockingRbkClient::new("192.168.8.114")?;
+/// let response =
+///     client.request(BatteryStatusRequest::new(), Duration::from_secs(10))?;
+///
+/// println!("Battery level: {}", response.battery_level);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BlockingRbkClient {
+    client: RbkClient,
+    handle: Handle,
+    shutdown: Option<oneshot::Sender<()>>,
+    runtime_thread: Option<JoinHandle<()>>,
+}
+
+impl BlockingRbkClient {
+    /// Create a new blocking client for the given host
+    ///
+    /// Fails if the runtime or its thread cannot be created.
+    pub fn new(host: impl Into<String>) -> RbkResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let handle = runtime.handle().clone();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+
+        let runtime_thread = std::thread::Builder::new()
+            .name("rbk-blocking-runtime".to_string())
+            .spawn(move || {
+                // Keep driving IO, timers and the read tasks until dropped
+                let _ = runtime.block_on(shutdown_rx);
+            })?;
+
+        Ok(Self {
+            client: RbkClient::new(host),
+            handle,
+            shutdown: Some(shutdown),
+            runtime_thread: Some(runtime_thread),
+        })
+    }
+
+    /// Access the wrapped async client
+    pub fn as_async(&self) -> &RbkClient {
+        &self.client
+    }
+
+    /// See [`RbkClient::connect_all`]
+    pub fn connect_all(&self) -> RbkResult<()> {
+        self.block_on(self.client.connect_all())
+    }
+
+    /// See [`RbkClient::health`]
+    pub fn health(&self) -> HashMap<u16, bool> {
+        self.block_on(self.client.health())
+    }
+
+    /// See [`RbkClient::health_probe`]
+    pub fn health_probe(&self, timeout: Duration) -> HashMap<u16, bool> {
+        self.block_on(self.client.health_probe(timeout))
+    }
+
+    /// See [`RbkClient::request`]
+    pub fn request<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+    ) -> RbkResult<T::Response>
+    where
+        T: ToRequestBody + FromResponseBody,
+    {
+        self.block_on(self.client.request(request, timeout))
+    }
+
+    /// See [`RbkClient::request_by`]
+    pub fn request_by<T>(
+        &self,
+        request: T,
+        deadline: std::time::Instant,
+    ) -> RbkResult<T::Response>
+    where
+        T: ToRequestBody + FromResponseBody,
+    {
+        self.block_on(self.client.request_by(request, deadline))
+    }
+
+    /// See [`RbkClient::request_with_meta`]
+    pub fn request_with_meta<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+    ) -> RbkResult<ResponseMeta<T::Response>>
+    where
+        T: ToRequestBody + FromResponseBody,
+    {
+        self.block_on(self.client.request_with_meta(request, timeout))
+    }
+
+    /// See [`RbkClient::request_raw`]
+    pub fn request_raw<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+    ) -> RbkResult<T::Response>
+    where
+        T: ToRawRequestBody + FromRawResponseBody,
+    {
+        self.block_on(self.client.request_raw(request, timeout))
+    }
+
+    /// See [`RbkClient::navigate_and_wait`]
+    pub fn navigate_and_wait(
+        &self,
+        target: MoveToTarget,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<TaskStatus> {
+        self.block_on(self.client.navigate_and_wait(
+            target,
+            poll_interval,
+            overall_timeout,
+        ))
+    }
+
+    /// See [`RbkClient::navigate_and_wait_or_cancel`]
+    pub fn navigate_and_wait_or_cancel(
+        &self,
+        target: MoveToTarget,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<TaskStatus> {
+        self.block_on(self.client.navigate_and_wait_or_cancel(
+            target,
+            poll_interval,
+            overall_timeout,
+        ))
+    }
+
+    /// See [`RbkClient::charge_until`]
+    pub fn charge_until(
+        &self,
+        level: f64,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<BatteryStatus> {
+        self.block_on(self.client.charge_until(
+            level,
+            poll_interval,
+            overall_timeout,
+        ))
+    }
+
+    /// See [`RbkClient::unsolicited_frames`], receive with
+    /// `blocking_recv`
+    pub fn unsolicited_frames(&self) -> broadcast::Receiver<RbkFrame> {
+        self.client.unsolicited_frames()
+    }
+
+    /// See [`RbkClient::connection_events`], receive with
+    /// `blocking_recv`
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.client.connection_events()
+    }
+
+    /// See [`RbkClient::send_no_reply`]
+    pub fn send_no_reply<T>(&self, request: T) -> RbkResult<()>
+    where
+        T: ToRequestBody,
+    {
+        self.block_on(self.client.send_no_reply(request))
+    }
+
+    /// See [`RbkClient::diagnostics`]
+    pub fn diagnostics(&self) -> Diagnostics {
+        self.block_on(self.client.diagnostics())
+    }
+
+    /// See [`RbkClient::emergency_stop_all`]
+    pub fn emergency_stop_all(&self) -> Vec<RbkResult<()>> {
+        self.block_on(self.client.emergency_stop_all())
+    }
+
+    /// See [`RbkClient::diagnostics_bundle`]
+    pub fn diagnostics_bundle(&self, timeout: Duration) -> DiagnosticsBundle {
+        self.block_on(self.client.diagnostics_bundle(timeout))
+    }
+
+    /// See [`RbkClient::ping`]
+    pub fn ping(&self, timeout: Duration) -> RbkResult<Duration> {
+        self.block_on(self.client.ping(timeout))
+    }
+
+    /// See [`RbkClient::ping_all`]
+    pub fn ping_all(
+        &self,
+        timeout: Duration,
+    ) -> HashMap<u16, RbkResult<Duration>> {
+        self.block_on(self.client.ping_all(timeout))
+    }
+
+    /// See [`RbkClient::query_all`]
+    pub fn query_all(&self, timeout: Duration) -> RbkResult<RobotSnapshot> {
+        self.block_on(self.client.query_all(timeout))
+    }
+
+    /// See [`RbkClient::wait_for_task`]
+    pub fn wait_for_task(
+        &self,
+        task_id: impl Into<crate::api::TaskId>,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> RbkResult<TaskStatus> {
+        self.block_on(self.client.wait_for_task(
+            task_id,
+            poll_interval,
+            timeout,
+        ))
+    }
+
+    /// See [`RbkClient::confirm_location`]
+    pub fn confirm_location(
+        &self,
+        timeout: Duration,
+    ) -> RbkResult<ConfirmResult> {
+        self.block_on(self.client.confirm_location(timeout))
+    }
+
+    /// See [`RbkClient::close`]
+    pub fn close(&self) -> RbkResult<()> {
+        self.block_on(self.client.close())
+    }
+
+    /// See [`RbkClient::configure_push`]
+    pub fn configure_push(
+        &self,
+        config: PushConfig,
+        timeout: Duration,
+    ) -> RbkResult<()> {
+        self.block_on(self.client.configure_push(config, timeout))
+    }
+
+    /// See [`RbkClient::push_config`]
+    pub fn push_config(&self) -> Option<PushConfig> {
+        self.client.push_config()
+    }
+
+    /// See [`RbkClient::run_sequence`]
+    pub fn run_sequence(
+        &self,
+        steps: Vec<(Box<dyn DynRequest>, Duration)>,
+    ) -> Vec<RbkResult<serde_json::Value>> {
+        self.block_on(self.client.run_sequence(steps))
+    }
+
+    /// See [`RbkClient::set_area_speed_limit`]
+    pub fn set_area_speed_limit(
+        &self,
+        plugin: impl Into<String>,
+        param: impl Into<String>,
+        speed: f64,
+    ) -> RbkResult<()> {
+        self.block_on(self.client.set_area_speed_limit(plugin, param, speed))
+    }
+
+    /// See [`RbkClient::run_transport`]
+    pub fn run_transport(&self, job: TransportJob) -> TransportResult {
+        self.block_on(self.client.run_transport(job))
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        // The runtime thread drives IO and timers, the future itself is
+        // polled on the calling thread
+        self.handle.block_on(future)
+    }
+}
+
+impl Drop for BlockingRbkClient {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        if let Some(thread) = self.runtime_thread.take()
+            && thread.join().is_err()
+        {
+            tracing::error!("Blocking runtime thread panicked");
+        }
+    }
+}
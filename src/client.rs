@@ -1,7 +1,18 @@
-use crate::api::ApiRequest;
+use crate::api::{ApiRequest, DynRequest, StateApi};
 use crate::error::{RbkError, RbkResult};
-use crate::port_client::RbkPortClient;
+use crate::frame::RbkFrame;
+use crate::port_client::{CircuitBreaker, PortOptions, RbkPortClient};
+use crate::transport::Transport;
+use futures_util::{Stream, StreamExt};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, trace, warn};
 
 // Port constants for different API categories
 const STATE_PORT: u16 = 19204;
@@ -11,15 +22,500 @@ const CONFIG_PORT: u16 = 19207;
 const KERNEL_PORT: u16 = 19208;
 const MISC_PORT: u16 = 19210;
 
+/// Time allowed for each command of [`RbkClient::emergency_stop_all`]
+const EMERGENCY_STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout of a request given a zero timeout
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Navigation status polling interval of a [`NavigationHandle`] by default
+const DEFAULT_NAVIGATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time each step of a [`TransportJob`] is given by default
+const DEFAULT_TRANSPORT_STEP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Shortest interval accepted by [`RbkClient::poll`]
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Hosts probed at the same time by [`RbkClient::discover`]
+const DISCOVERY_CONCURRENCY: usize = 64;
+
+/// `timeout`, or [`DEFAULT_REQUEST_TIMEOUT`] if it is zero
+fn request_timeout(timeout: Duration) -> Duration {
+    if timeout.is_zero() {
+        DEFAULT_REQUEST_TIMEOUT
+    } else {
+        timeout
+    }
+}
+
+/// Response returned by [`RbkClient::request_with_meta`] together with the
+/// header fields of the frame it arrived in
+///
+/// Also meant as a testing aid for record/replay harnesses: flow numbers
+/// are assigned per port, counting up from 1 and wrapping to 0 after 511
+/// while skipping numbers still in flight, and the robot echoes them
+/// unchanged. Recording `flow_no` and `api_no` alongside `raw_body` is
+/// enough to match captured frames against replayed requests without
+/// pinning the flow numbers the client hands out.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta<T> {
+    /// Flow number echoed by the robot
+    pub flow_no: u16,
+    /// API number of the response frame, usually the request's plus 10000
+    pub api_no: u16,
+    /// Response body as received, decoded lossily as UTF-8
+    pub raw_body: String,
+    /// Deserialized response
+    pub parsed: T,
+}
+
+/// Outcome of [`RbkClient::confirm_location`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmResult {
+    /// Whether the robot reports itself localized after the confirmation
+    pub confirmed: bool,
+    /// Localization confidence (0.0 to 1.0), known only once confirmed
+    pub confidence: Option<f64>,
+}
+
+/// State of the robot queried at one point in time, see
+/// [`RbkClient::query_all`]
+///
+/// Serializable so telemetry can be written as JSON lines and replayed or
+/// inspected offline. `version` changes whenever fields are changed
+/// incompatibly.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RobotSnapshot {
+    /// Layout version of the record, [`RobotSnapshot::VERSION`] when taken
+    pub version: u32,
+    /// When the queries were sent, milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    pub pose: crate::api::RobotPose,
+    pub battery: crate::api::BatteryStatus,
+    pub nav: crate::api::NavStatus,
+    pub block: crate::api::BlockStatus,
+}
+
+impl RobotSnapshot {
+    /// Layout version of snapshots taken by this SDK version
+    pub const VERSION: u32 = 1;
+}
+
+/// IMU, encoder and motor state for maintenance tools, see
+/// [`RbkClient::diagnostics_bundle`]
+///
+/// A subsystem is `None` if its query failed or the robot reported an
+/// error for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticsBundle {
+    pub imu: Option<crate::api::ImuData>,
+    pub encoders: Option<crate::api::EncoderStatus>,
+    pub motors: Option<crate::api::MotorStatus>,
+}
+
+/// Navigation started with [`RbkClient::start_navigation`]
+///
+/// Holds a clone of the client, so it can be moved to another task than
+/// the one which started the navigation. Dropping the handle leaves the
+/// navigation running.
+#[derive(Clone)]
+pub struct NavigationHandle {
+    client: RbkClient,
+    target: crate::PointId,
+    poll_interval: Duration,
+}
+
+impl NavigationHandle {
+    /// Poll the navigation status this often, every 500 ms by default
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Wait until the navigation reaches a terminal state, which is
+    /// returned
+    ///
+    /// Fails with [`RbkError::Timeout`] if `timeout` elapses first, the
+    /// navigation keeps running then.
+    pub async fn await_completion(
+        &self,
+        timeout: Duration,
+    ) -> RbkResult<crate::api::TaskStatus> {
+        tokio::time::timeout(
+            timeout,
+            self.client
+                .wait_for_navigation(&self.target, self.poll_interval),
+        )
+        .await
+        .map_err(|_| RbkError::Timeout)?
+    }
+
+    /// Cancel the navigation (API 3003)
+    pub async fn cancel(&self) -> RbkResult<()> {
+        self.client
+            .request(crate::api::CancelTaskRequest::new(), Duration::ZERO)
+            .await?
+            .into_result()
+            .map(drop)
+    }
+}
+
+/// Load, move and unload cycle run by [`RbkClient::run_transport`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportJob {
+    /// Station to pick the load up at
+    pub pickup: String,
+    /// Station to drop the load off at
+    pub dropoff: String,
+    /// How often navigation and jack status are polled, every 500 ms by
+    /// default
+    pub poll_interval: Duration,
+    /// Time each step is given to finish, 120 seconds by default
+    pub step_timeout: Duration,
+}
+
+impl TransportJob {
+    pub fn new(pickup: impl Into<String>, dropoff: impl Into<String>) -> Self {
+        Self {
+            pickup: pickup.into(),
+            dropoff: dropoff.into(),
+            poll_interval: DEFAULT_NAVIGATION_POLL_INTERVAL,
+            step_timeout: DEFAULT_TRANSPORT_STEP_TIMEOUT,
+        }
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn step_timeout(mut self, timeout: Duration) -> Self {
+        self.step_timeout = timeout;
+        self
+    }
+}
+
+/// Step of a [`TransportJob`], in the order they are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportStep {
+    MoveToPickup,
+    Load,
+    MoveToDropoff,
+    Unload,
+}
+
+impl TransportStep {
+    const ALL: [TransportStep; 4] = [
+        TransportStep::MoveToPickup,
+        TransportStep::Load,
+        TransportStep::MoveToDropoff,
+        TransportStep::Unload,
+    ];
+}
+
+/// Outcome of [`RbkClient::run_transport`]
+///
+/// Holds the result of every step which was run, in order. The job stops
+/// at the first failing step, the steps after it are missing.
+#[derive(Debug)]
+pub struct TransportResult {
+    pub steps: Vec<(TransportStep, RbkResult<()>)>,
+}
+
+impl TransportResult {
+    /// Whether every step was run and succeeded
+    pub fn is_complete(&self) -> bool {
+        self.steps.len() == TransportStep::ALL.len()
+            && self.steps.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// The step which failed and its error, if any
+    pub fn failed_step(&self) -> Option<(TransportStep, &RbkError)> {
+        self.steps.iter().find_map(|(step, result)| {
+            result.as_ref().err().map(|e| (*step, e))
+        })
+    }
+}
+
+/// Robot answering on the network, see [`RbkClient::discover`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredRobot {
+    /// Address the robot answered on
+    pub host: Ipv4Addr,
+    pub id: String,
+    pub model: String,
+    pub version: String,
+}
+
+/// Health of every port, see [`RbkClient::diagnostics`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostics {
+    /// Keyed by port number
+    pub per_port: HashMap<u16, PortDiag>,
+}
+
+/// Health of a single port
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortDiag {
+    /// Frames received without a pending request, such as push frames,
+    /// late responses and replies to [`RbkClient::send_no_reply`]
+    pub unmatched_responses: u64,
+    /// Requests currently waiting for their response
+    pub pending_requests: usize,
+    /// Connections opened after the first one
+    pub reconnects: u64,
+    /// Most recent error of a request or connect on the port
+    pub last_error: Option<String>,
+}
+
+/// Change of a port connection, see [`RbkClient::connection_events`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// A connection to the port was opened
+    Connected { port: u16 },
+    /// Opening a connection failed, another attempt follows
+    Reconnecting {
+        port: u16,
+        /// Number of the attempt about to be made, starting at 2
+        attempt: u32,
+        /// Why the previous attempt failed
+        error: String,
+    },
+    /// The connection was closed by the robot or failed
+    Disconnected { port: u16 },
+}
+
+/// Builder for [`RbkClient`], created by [`RbkClient::builder`]
+pub struct RbkClientBuilder {
+    host: String,
+    options: PortOptions,
+    auto_lock: Option<String>,
+    nested_status_header: bool,
+}
+
+impl RbkClientBuilder {
+    /// Open connections through a custom transport instead of TCP
+    ///
+    /// The factory is called with the robot port whenever a port client
+    /// (re)connects and must return an already connected stream, for
+    /// example through an SSH tunnel or a unix socket bridge. The host
+    /// passed to the builder is not used for connecting then.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, Transport};
+    /// use tokio::net::TcpStream;
+    ///
+    /// // Robot ports forwarded to local ports offset by 10000
+    /// let client = RbkClient::builder("192.168.8.114")
+    ///     .transport(|port| async move {
+    ///         let stream = TcpStream::connect(("127.0.0.1", port - 10000)).await?;
+    ///         Ok(Box::new(stream) as Box<dyn Transport>)
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn transport<F, Fut>(mut self, factory: F) -> Self
+    where
+        F: Fn(u16) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::io::Result<Box<dyn Transport>>>
+            + Send
+            + 'static,
+    {
+        self.options.connector =
+            Some(Arc::new(move |port| Box::pin(factory(port))));
+        self
+    }
+
+    /// Cap the responses each port keeps for requests which did not pick
+    /// them up yet, 256 by default
+    ///
+    /// Once the cap is reached the oldest buffered response is dropped
+    /// with a warning, so a robot flooding responses cannot exhaust memory.
+    pub fn max_buffered_responses(mut self, max: usize) -> Self {
+        self.options.max_buffered_responses = max;
+        self
+    }
+
+    /// Give up opening a connection after `timeout`, 10 seconds by default
+    ///
+    /// Applies to every connection attempt, see
+    /// [`RbkClientBuilder::connect_attempts`]. Running out of time fails
+    /// the request with [`RbkError::ConnectTimeout`], while the timeout
+    /// passed to each request only covers waiting for the response and
+    /// fails with [`RbkError::ResponseTimeout`].
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.options.connect_timeout = timeout;
+        self
+    }
+
+    /// Retry opening a connection up to `attempts` times in total, 3 by
+    /// default
+    ///
+    /// Robots bring up their ports one by one after booting, so a port may
+    /// refuse connections for a moment while others already accept them.
+    /// The first retry waits `backoff` (250 ms by default), every further
+    /// retry twice as long as the one before. Each retry is announced as
+    /// [`ConnectionEvent::Reconnecting`]. Once all attempts failed the
+    /// request fails with [`RbkError::ConnectAttemptsFailed`] holding the
+    /// error of every attempt, unless only one attempt was made.
+    pub fn connect_attempts(
+        mut self,
+        attempts: u32,
+        backoff: Duration,
+    ) -> Self {
+        self.options.connect_attempts = attempts.max(1);
+        self.options.connect_backoff = backoff;
+        self
+    }
+
+    /// Close a port's connection once no request was sent on it for
+    /// `timeout`, disabled by default
+    ///
+    /// Saves sockets on the robot for clients which only send a request
+    /// now and then. The next request on the port opens a new connection.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.options.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Take the control lock automatically under `nick_name`
+    ///
+    /// Robots reject control and navigation commands unless the client
+    /// holds the control lock. With auto-lock the lock is taken right
+    /// before the first such request, and again once the connection it was
+    /// taken on is lost, and released by [`RbkClient::close`]. A robot
+    /// refusing to hand over control fails that request with
+    /// [`RbkError::LockUnavailable`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// let client = RbkClient::builder("192.168.8.114")
+    ///     .auto_lock("dispatcher")
+    ///     .build();
+    /// ```
+    pub fn auto_lock(mut self, nick_name: impl Into<String>) -> Self {
+        self.auto_lock = Some(nick_name.into());
+        self
+    }
+
+    /// Stop dialing a port after `failures` failed connects in a row,
+    /// disabled by default
+    ///
+    /// While the breaker is open, requests to the port fail right away
+    /// with [`RbkError::CircuitOpen`] instead of hammering a robot which
+    /// is down with connection attempts. After `cooldown` one request is
+    /// let through to probe the port, closing the breaker again when it
+    /// connects. A failed connect counts once, no matter how many
+    /// [`RbkClientBuilder::connect_attempts`] it made.
+    pub fn circuit_breaker(
+        mut self,
+        failures: u32,
+        cooldown: Duration,
+    ) -> Self {
+        self.options.circuit_breaker = Some(CircuitBreaker {
+            failures: failures.max(1),
+            cooldown,
+        });
+        self
+    }
+
+    /// Collect frames written to a port within `window` into a single
+    /// write, disabled by default
+    ///
+    /// Saves a syscall per frame when sending bursts of high frequency
+    /// commands such as open loop motion updates in a teleop loop. Frames
+    /// keep their order, but each request may be delayed by up to
+    /// `window` before it goes out, so keep it short, e.g. 5 ms.
+    pub fn write_coalescing(mut self, window: Duration) -> Self {
+        self.options.write_coalescing = Some(window);
+        self
+    }
+
+    /// Accept responses nesting their status under a `header` object, as
+    /// sent by newer firmware, disabled by default
+    ///
+    /// Such responses look like `{"header": {"ret_code": 0, "err_msg": ""},
+    /// "data": {..}}`, they are flattened before parsing. Responses with
+    /// flat status fields are still parsed as before.
+    pub fn nested_status_header(mut self, enabled: bool) -> Self {
+        self.nested_status_header = enabled;
+        self
+    }
+
+    /// Reject responses announcing a body larger than `max` bytes, 64 MiB
+    /// by default
+    ///
+    /// A rejected response is discarded and its request fails with
+    /// [`RbkError::ProtocolError`]. Raise the limit for large map
+    /// transfers, lower it to bound the memory a misbehaving robot can make
+    /// the client allocate.
+    pub fn max_body_size(mut self, max: usize) -> Self {
+        self.options.max_body_size = max;
+        self
+    }
+
+    /// Fill the 6 reserved bytes of every request header with `reserved`,
+    /// all zeros by default
+    ///
+    /// Some deployments stamp a client or session id into these bytes, e.g.
+    /// to tell apart several clients controlling the same robot.
+    pub fn reserved(mut self, reserved: [u8; 6]) -> Self {
+        self.options.reserved = reserved;
+        self
+    }
+
+    /// Create the client, no connection is opened until the first request
+    pub fn build(self) -> RbkClient {
+        let port_client = |port| {
+            RbkPortClient::with_options(
+                self.host.clone(),
+                port,
+                self.options.clone(),
+            )
+        };
+
+        RbkClient {
+            config_client: port_client(CONFIG_PORT),
+            misc_client: port_client(MISC_PORT),
+            state_client: port_client(STATE_PORT),
+            control_client: port_client(CONTROL_PORT),
+            nav_client: port_client(NAV_PORT),
+            kernel_client: port_client(KERNEL_PORT),
+            unsolicited: self.options.unsolicited.clone(),
+            events: self.options.events.clone(),
+            push_config: Arc::new(std::sync::Mutex::new(None)),
+            nested_status_header: self.nested_status_header,
+            auto_lock: self.auto_lock.map(|nick_name| {
+                Arc::new(AutoLock {
+                    nick_name,
+                    held: tokio::sync::Mutex::new(None),
+                })
+            }),
+            host: self.host,
+        }
+    }
+}
+
 /// Main RBK client for communicating with robots
 ///
 /// This client manages multiple port clients for different API categories:
 /// - State APIs (1000-1999): port 19204
 /// - Control APIs (2000-2999): port 19205
 /// - Navigation APIs (3000-3999): port 19206
-/// - Config APIs (4000-5999): port 19207
-/// - Kernel APIs (7000-7999): port 19208
+/// - Config APIs (4000-4999): port 19207
+/// - Kernel APIs (5000-5999): port 19208
 /// - Misc APIs (6000-6998): port 19210
+/// - Push APIs (9000+): port 19210
+///
+/// Clones share the connections, lock and push configuration of the client
+/// they were cloned from.
+#[derive(Clone)]
 pub struct RbkClient {
     #[allow(dead_code)]
     host: String,
@@ -29,6 +525,20 @@ pub struct RbkClient {
     control_client: RbkPortClient,
     nav_client: RbkPortClient,
     kernel_client: RbkPortClient,
+    unsolicited: broadcast::Sender<RbkFrame>,
+    events: broadcast::Sender<ConnectionEvent>,
+    push_config: Arc<std::sync::Mutex<Option<crate::api::PushConfig>>>,
+    auto_lock: Option<Arc<AutoLock>>,
+    nested_status_header: bool,
+}
+
+/// Control lock taken on demand, see [`RbkClientBuilder::auto_lock`]
+struct AutoLock {
+    nick_name: String,
+    /// Connection the lock was taken on, the lock is taken again once it
+    /// is gone as the robot may have handed the lock to someone else in
+    /// the meantime. Also serializes concurrent attempts
+    held: tokio::sync::Mutex<Option<u64>>,
 }
 
 impl RbkClient {
@@ -46,18 +556,308 @@ impl RbkClient {
     /// let client = RbkClient::new("192.168.8.114");
     /// ```
     pub fn new(host: impl Into<String>) -> Self {
-        let host = host.into();
-        //todo: block until connections are established
+        Self::builder(host).build()
+    }
 
-        Self {
-            config_client: RbkPortClient::new(host.clone(), CONFIG_PORT),
-            misc_client: RbkPortClient::new(host.clone(), MISC_PORT),
-            state_client: RbkPortClient::new(host.clone(), STATE_PORT),
-            control_client: RbkPortClient::new(host.clone(), CONTROL_PORT),
-            nav_client: RbkPortClient::new(host.clone(), NAV_PORT),
-            kernel_client: RbkPortClient::new(host.clone(), KERNEL_PORT),
-            host,
+    /// Create a builder to customize the client before creating it
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// let client = RbkClient::builder("192.168.8.114").build();
+    /// ```
+    pub fn builder(host: impl Into<String>) -> RbkClientBuilder {
+        RbkClientBuilder {
+            host: host.into(),
+            options: PortOptions::default(),
+            auto_lock: None,
+            nested_status_header: false,
+        }
+    }
+
+    /// Find robots among `hosts` by querying the robot information (API
+    /// 1000) on each of them
+    ///
+    /// Up to 64 hosts are probed at the same time. Robots which have not
+    /// answered within `timeout` are left out, so the robots found so far
+    /// are returned once it elapses.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    /// use std::net::Ipv4Addr;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// // Every host of 192.168.8.0/24
+    /// let hosts = (1..=254).map(|host| Ipv4Addr::new(192, 168, 8, host));
+    /// for robot in RbkClient::discover(hosts, Duration::from_secs(2)).await {
+    ///     println!("{} at {} ({})", robot.id, robot.host, robot.model);
+    /// }
+    /// # }
+    /// ```
+    pub async fn discover(
+        hosts: impl IntoIterator<Item = Ipv4Addr, IntoIter: Send>,
+        timeout: Duration,
+    ) -> Vec<DiscoveredRobot> {
+        discover_on_port(hosts, STATE_PORT, timeout).await
+    }
+
+    /// Eagerly connect to every port of the robot
+    ///
+    /// By default connections are established lazily on the first request.
+    /// Calling this right after [`RbkClient::new`] surfaces network problems
+    /// at startup instead. All ports are dialed concurrently, ports which
+    /// are already connected are left untouched, so the method can also be
+    /// used to re-establish connections after a failure.
+    ///
+    /// # Returns
+    ///
+    /// Returns [`RbkError::PortsUnreachable`] with the error of every port
+    /// which could not be connected. The remaining ports stay connected and
+    /// usable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, RbkError};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    ///
+    /// if let Err(RbkError::PortsUnreachable(failed)) = client.connect_all().await {
+    ///     for (port, e) in failed {
+    ///         eprintln!("Port {} is unreachable: {}", port, e);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_all(&self) -> RbkResult<()> {
+        let clients = self.port_clients();
+
+        let (state, control, nav, config, kernel, misc) = tokio::join!(
+            clients[0].ensure_connected(),
+            clients[1].ensure_connected(),
+            clients[2].ensure_connected(),
+            clients[3].ensure_connected(),
+            clients[4].ensure_connected(),
+            clients[5].ensure_connected(),
+        );
+
+        let failed: Vec<(u16, RbkError)> = clients
+            .iter()
+            .zip([state, control, nav, config, kernel, misc])
+            .filter_map(|(client, result)| {
+                result.err().map(|e| (client.port(), e))
+            })
+            .collect();
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(RbkError::PortsUnreachable(failed))
+        }
+    }
+
+    /// Report the connection state of every port without touching the
+    /// network
+    ///
+    /// A port is considered connected when it holds an open connection which
+    /// has not been reset after a failure. Ports are connected lazily, so a
+    /// freshly created client reports all ports as disconnected until
+    /// [`RbkClient::connect_all`] or a request is issued.
+    pub async fn health(&self) -> HashMap<u16, bool> {
+        let mut health = HashMap::new();
+
+        for client in self.port_clients() {
+            health.insert(client.port(), client.is_connected().await);
+        }
+
+        health
+    }
+
+    /// Actively probe every port with a robot info query (API 1000)
+    ///
+    /// Any reply, including an error status for an API the port does not
+    /// serve, proves the port is reachable. Disconnected ports are dialed
+    /// first, ports which fail to answer within `timeout` are reported as
+    /// unhealthy.
+    pub async fn health_probe(&self, timeout: Duration) -> HashMap<u16, bool> {
+        let clients = self.port_clients();
+        let api_no = StateApi::Info as u16;
+
+        let (state, control, nav, config, kernel, misc) = tokio::join!(
+            clients[0].request(api_no, &[], timeout),
+            clients[1].request(api_no, &[], timeout),
+            clients[2].request(api_no, &[], timeout),
+            clients[3].request(api_no, &[], timeout),
+            clients[4].request(api_no, &[], timeout),
+            clients[5].request(api_no, &[], timeout),
+        );
+
+        clients
+            .iter()
+            .zip([state, control, nav, config, kernel, misc])
+            .map(|(client, result)| (client.port(), result.is_ok()))
+            .collect()
+    }
+
+    /// Counters and last error of every port, for health panels and
+    /// diagnosing a misbehaving robot
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// for (port, diag) in client.diagnostics().await.per_port {
+    ///     println!(
+    ///         "{}: {} unmatched, {} reconnects, last error {:?}",
+    ///         port, diag.unmatched_responses, diag.reconnects, diag.last_error
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    pub async fn diagnostics(&self) -> Diagnostics {
+        let mut per_port = HashMap::new();
+        for client in self.port_clients() {
+            per_port.insert(client.port(), client.diagnostics().await);
+        }
+
+        Diagnostics { per_port }
+    }
+
+    /// Query IMU (1014), encoder (1018) and motor (1040) state at once
+    ///
+    /// The queries are sent one after another, each with the given
+    /// `timeout` (defaults to 10 seconds if zero). Unlike
+    /// [`RbkClient::query_all`] a failing query does not fail the others,
+    /// its subsystem is left empty instead. A query running into its
+    /// timeout closes the state port connection, so sending them
+    /// concurrently would fail the others along with it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let bundle = client.diagnostics_bundle(Duration::from_secs(5)).await;
+    /// for motor in bundle.motors.map(|m| m.motors).unwrap_or_default() {
+    ///     println!("{}: {} °C", motor.motor_name, motor.temperature);
+    /// }
+    /// # }
+    /// ```
+    pub async fn diagnostics_bundle(
+        &self,
+        timeout: Duration,
+    ) -> DiagnosticsBundle {
+        let imu = self
+            .request(crate::api::RobotImuRequest::new(), timeout)
+            .await
+            .and_then(|imu| imu.into_result());
+        let encoders = self
+            .request(crate::api::RobotEncoderStatusRequest::new(), timeout)
+            .await
+            .and_then(|encoders| encoders.into_result());
+        let motors = self
+            .request(crate::api::RobotMotorStatusRequest::new(), timeout)
+            .await
+            .and_then(|motors| motors.into_result());
+
+        DiagnosticsBundle {
+            imu: imu.ok(),
+            encoders: encoders.ok(),
+            motors: motors.ok(),
+        }
+    }
+
+    /// JSON of a response body, flattened if the client accepts nested
+    /// status headers
+    fn response_json<'a>(&self, body: &'a [u8]) -> Cow<'a, [u8]> {
+        let body = json_body(body);
+        if !self.nested_status_header {
+            return Cow::Borrowed(body);
+        }
+
+        let Ok(mut value) = serde_json::from_slice(body) else {
+            return Cow::Borrowed(body);
+        };
+        if !crate::api::flatten_status_header(&mut value) {
+            return Cow::Borrowed(body);
         }
+        Cow::Owned(value.to_string().into_bytes())
+    }
+
+    /// Measure the round-trip time of a cheap query on the state port
+    ///
+    /// Sends a robot information query (API 1000) and returns how long the
+    /// answer took. Connecting is not part of the measurement, the port is
+    /// connected first if needed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let rtt = client.ping(Duration::from_secs(1)).await?;
+    ///
+    /// println!("Round trip: {:?}", rtt);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self, timeout: Duration) -> RbkResult<Duration> {
+        ping_port(&self.state_client, timeout).await
+    }
+
+    /// Same as [`RbkClient::ping`] for every port, keyed by port number
+    ///
+    /// The robot information query (API 1000) is sent to every port, as
+    /// most ports serve no query without side effects. Ports other than the
+    /// state port answer it with an error, which still counts as a round
+    /// trip: only a port that doesn't answer at all yields an error here.
+    pub async fn ping_all(
+        &self,
+        timeout: Duration,
+    ) -> HashMap<u16, RbkResult<Duration>> {
+        let clients = self.port_clients();
+
+        let (state, control, nav, config, kernel, misc) = tokio::join!(
+            ping_port(clients[0], timeout),
+            ping_port(clients[1], timeout),
+            ping_port(clients[2], timeout),
+            ping_port(clients[3], timeout),
+            ping_port(clients[4], timeout),
+            ping_port(clients[5], timeout),
+        );
+
+        clients
+            .iter()
+            .map(|client| client.port())
+            .zip([state, control, nav, config, kernel, misc])
+            .collect()
+    }
+
+    fn port_clients(&self) -> [&RbkPortClient; 6] {
+        [
+            &self.state_client,
+            &self.control_client,
+            &self.nav_client,
+            &self.config_client,
+            &self.kernel_client,
+            &self.misc_client,
+        ]
     }
 
     /// Send a request to the robot
@@ -94,67 +894,2628 @@ impl RbkClient {
     where
         T: crate::api::ToRequestBody + crate::api::FromResponseBody,
     {
-        let timeout = if timeout.is_zero() {
-            Duration::from_secs(10)
-        } else {
-            timeout
-        };
+        self.send_request(request, timeout, true).await
+    }
+
+    /// [`RbkClient::request`], taking the control lock first only if
+    /// `auto_lock` is set and the client was built with
+    /// [`RbkClientBuilder::auto_lock`]
+    async fn send_request<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+        auto_lock: bool,
+    ) -> RbkResult<T::Response>
+    where
+        T: crate::api::ToRequestBody + crate::api::FromResponseBody,
+    {
+        let timeout = request_timeout(timeout);
 
         let api = request.to_api_request();
         let request_str = request
             .to_request_body()
             .map_err(|e| RbkError::ParseError(e.to_string()))?;
-        let api_no = api.api_no();
+        trace!(
+            "API {} request body: {}",
+            api.api_no(),
+            loggable_body(&request_str, request.sensitive())
+        );
 
-        let response_str = match api {
-            ApiRequest::State(_) => {
-                self.state_client
-                    .request(api_no, &request_str, timeout)
-                    .await?
-            }
-            ApiRequest::Control(_) => {
-                self.control_client
-                    .request(api_no, &request_str, timeout)
-                    .await?
-            }
-            ApiRequest::Nav(_) => {
-                self.nav_client
-                    .request(api_no, &request_str, timeout)
+        let client = if auto_lock {
+            self.port_client_for(api, timeout).await?
+        } else {
+            self.port_client(api)
+        };
+        let response_body = client
+            .request(api.api_no(), request_str.as_bytes(), timeout)
+            .await?;
+
+        serde_json::from_slice(&self.response_json(&response_body)).map_err(
+            |e| RbkError::response_parse(api.api_no(), &response_body, e),
+        )
+    }
+
+    /// Same as [`RbkClient::request`], but the response has to arrive by
+    /// `deadline` instead of within a timeout
+    ///
+    /// Suits callers working against an outer deadline, such as the budget
+    /// of an incoming RPC. Both `std` and `tokio` instants are accepted.
+    /// Connecting and taking the control lock count against the deadline
+    /// as well, once it passes the request fails with
+    /// [`RbkError::Timeout`]. A deadline already past fails without sending
+    /// anything.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{BatteryStatusRequest, CommonInfoRequest, RbkClient};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let deadline = Instant::now() + Duration::from_secs(2);
+    /// let info = client.request_by(CommonInfoRequest::new(), deadline).await?;
+    /// let battery =
+    ///     client.request_by(BatteryStatusRequest::new(), deadline).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_by<T>(
+        &self,
+        request: T,
+        deadline: impl Into<tokio::time::Instant>,
+    ) -> RbkResult<T::Response>
+    where
+        T: crate::api::ToRequestBody + crate::api::FromResponseBody,
+    {
+        let deadline = deadline.into();
+        let remaining =
+            deadline.saturating_duration_since(tokio::time::Instant::now());
+        // A zero timeout would fall back to the default one
+        if remaining.is_zero() {
+            return Err(RbkError::Timeout);
+        }
+
+        tokio::time::timeout_at(deadline, self.request(request, remaining))
+            .await
+            .map_err(|_| RbkError::Timeout)?
+    }
+
+    /// Subscribe to frames which do not answer a pending request
+    ///
+    /// Robots with push enabled on a request port interleave push frames
+    /// with responses, these are delivered here together with late
+    /// responses to abandoned requests and the replies to
+    /// [`RbkClient::send_no_reply`], from every port. Frames arriving
+    /// while nobody is subscribed are dropped, a receiver falling behind
+    /// by more than 64 frames skips the oldest ones.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let mut frames = client.unsolicited_frames();
+    ///
+    /// while let Ok(frame) = frames.recv().await {
+    ///     println!("API {}: {}", frame.api_no, String::from_utf8_lossy(&frame.body));
+    /// }
+    /// # }
+    /// ```
+    pub fn unsolicited_frames(&self) -> broadcast::Receiver<RbkFrame> {
+        self.unsolicited.subscribe()
+    }
+
+    /// Subscribe to connection changes of every port
+    ///
+    /// Events raised while nobody is subscribed are dropped, a receiver
+    /// falling behind by more than 64 events skips the oldest ones.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{ConnectionEvent, RbkClient};
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let mut events = client.connection_events();
+    ///
+    /// while let Ok(event) = events.recv().await {
+    ///     if let ConnectionEvent::Reconnecting { port, attempt, error } = event {
+    ///         println!("port {} attempt {}: {}", port, attempt, error);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Send a request without waiting for the robot's response
+    ///
+    /// Only writes the request, saving the round trip for high frequency
+    /// commands such as open loop motion updates in a teleop loop. The
+    /// robot still replies, the response is discarded when it arrives.
+    /// Errors reported by the robot go unnoticed, so use
+    /// [`RbkClient::request`] for anything that has to be acknowledged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{OpenLoopMotionRequest, RbkClient};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// client.send_no_reply(OpenLoopMotionRequest::new()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_no_reply<T>(&self, request: T) -> RbkResult<()>
+    where
+        T: crate::api::ToRequestBody,
+    {
+        let api = request.to_api_request();
+        let request_str = request
+            .to_request_body()
+            .map_err(|e| RbkError::ParseError(e.to_string()))?;
+        trace!(
+            "API {} request body (no reply): {}",
+            api.api_no(),
+            loggable_body(&request_str, request.sensitive())
+        );
+
+        self.port_client_for(api, DEFAULT_REQUEST_TIMEOUT)
+            .await?
+            .send(api.api_no(), request_str.as_bytes())
+            .await
+    }
+
+    /// Send a request to the robot unless `token` is cancelled first
+    ///
+    /// Behaves like [`RbkClient::request`], but gives up with
+    /// [`RbkError::Cancelled`] as soon as the token is cancelled. The flow
+    /// number of the abandoned request is released and a late response to
+    /// it is discarded, so the connection stays usable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, BatteryStatusRequest};
+    /// use std::time::Duration;
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let shutdown = CancellationToken::new();
+    /// let response = client
+    ///     .request_cancellable(
+    ///         BatteryStatusRequest::new(),
+    ///         Duration::from_secs(10),
+    ///         shutdown.clone(),
+    ///     )
+    ///     .await?;
+    ///
+    /// println!("Battery status response: {:?}", response);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_cancellable<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+        token: CancellationToken,
+    ) -> RbkResult<T::Response>
+    where
+        T: crate::api::ToRequestBody + crate::api::FromResponseBody,
+    {
+        // Dropping the request future releases its flow number
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(RbkError::Cancelled),
+            result = self.request(request, timeout) => result,
+        }
+    }
+
+    /// Stop everything the robot is doing, for panic buttons
+    ///
+    /// Engages the software emergency stop (6004), cancels navigation
+    /// (3003), stops open loop motion (2000) and stops the jack (6072), all
+    /// concurrently. A failing command does not keep the others from being
+    /// sent. Returns the result of every command in that order, a robot
+    /// error counts as failure. Each command is given 2 seconds including
+    /// connecting, after which it fails with [`RbkError::Timeout`]. The
+    /// commands are sent without taking the control lock, even on a client
+    /// built with [`RbkClientBuilder::auto_lock`].
+    ///
+    /// The emergency stop stays engaged until released with
+    /// [`SoftEmcRequest`](crate::api::SoftEmcRequest).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// for result in client.emergency_stop_all().await {
+    ///     if let Err(e) = result {
+    ///         eprintln!("Stop command failed: {}", e);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn emergency_stop_all(&self) -> Vec<RbkResult<()>> {
+        let timeout = EMERGENCY_STOP_TIMEOUT;
+        let engage =
+            crate::api::SoftEmcRequest::new(crate::api::SoftEmc::new(true));
+        // Sent without taking the control lock, which another client may
+        // hold or which may be what is failing
+        let (soft_emc, cancel, stop, jack) = tokio::join!(
+            stop_command(self.send_request(engage, timeout, false)),
+            stop_command(self.send_request(
+                crate::api::CancelTaskRequest::new(),
+                timeout,
+                false,
+            )),
+            stop_command(self.send_request(
+                crate::api::StopExerciseRequest::new(),
+                timeout,
+                false,
+            )),
+            stop_command(self.send_request(
+                crate::api::StopJackRequest::new(),
+                timeout,
+                false,
+            )),
+        );
+
+        vec![soft_emc, cancel, stop, jack]
+    }
+
+    /// Query pose, battery, navigation and block status at once
+    ///
+    /// The queries are sent concurrently, each with the given `timeout`
+    /// (defaults to 10 seconds if zero). Fails if any of them fails or the
+    /// robot reports an error for it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let snapshot = client.query_all(Duration::from_secs(5)).await?;
+    ///
+    /// println!("{}", serde_json::to_string(&snapshot)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_all(
+        &self,
+        timeout: Duration,
+    ) -> RbkResult<RobotSnapshot> {
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        let (pose, battery, nav, block) = tokio::try_join!(
+            async {
+                self.request(crate::api::RobotPoseRequest::new(), timeout)
                     .await?
-            }
-            ApiRequest::Config(_) => {
-                self.config_client
-                    .request(api_no, &request_str, timeout)
+                    .into_result()
+            },
+            async {
+                self.request(crate::api::BatteryStatusRequest::new(), timeout)
                     .await?
-            }
-            ApiRequest::Peripheral(_) => {
-                self.misc_client
-                    .request(api_no, &request_str, timeout)
+                    .into_result()
+            },
+            async {
+                self.request(
+                    crate::api::NavStatusRequest::new(
+                        crate::api::GetNavStatus::new(),
+                    ),
+                    timeout,
+                )
+                .await?
+                .into_result()
+            },
+            async {
+                self.request(crate::api::BlockStatusRequest::new(), timeout)
                     .await?
+                    .into_result()
+            },
+        )?;
+
+        Ok(RobotSnapshot {
+            version: RobotSnapshot::VERSION,
+            timestamp_ms,
+            pose,
+            battery,
+            nav,
+            block,
+        })
+    }
+
+    /// Confirm the location found by relocation
+    ///
+    /// Sends [`ConfirmLocationRequest`], then queries the relocation status
+    /// to check that the robot accepted the confirmation. If it did, the
+    /// localization confidence is read from the current pose.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, RelocateRequest};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// client
+    ///     .request(RelocateRequest::new(), Duration::from_secs(5))
+    ///     .await?
+    ///     .into_result()?;
+    ///
+    /// let result = client.confirm_location(Duration::from_secs(5)).await?;
+    /// println!("confirmed={} confidence={:?}", result.confirmed, result.confidence);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ConfirmLocationRequest`]: crate::ConfirmLocationRequest
+    pub async fn confirm_location(
+        &self,
+        timeout: Duration,
+    ) -> RbkResult<ConfirmResult> {
+        self.request(crate::api::ConfirmLocationRequest::new(), timeout)
+            .await?
+            .into_result()?;
+
+        let status = self
+            .request(crate::api::RobotRelocationStatusRequest::new(), timeout)
+            .await?
+            .into_result()?;
+        if status.state != crate::api::RelocState::Success {
+            debug!("Location not confirmed, state {:?}", status.state);
+            return Ok(ConfirmResult {
+                confirmed: false,
+                confidence: None,
+            });
+        }
+
+        let pose = self
+            .request(crate::api::RobotPoseRequest::new(), timeout)
+            .await?
+            .into_result()?;
+
+        Ok(ConfirmResult {
+            confirmed: true,
+            confidence: Some(pose.confidence),
+        })
+    }
+
+    /// Release the control lock taken by auto-lock and close every
+    /// connection
+    ///
+    /// The client stays usable, the next request reconnects and, with
+    /// [`RbkClientBuilder::auto_lock`], takes the lock again. Connections
+    /// are closed even when releasing the lock fails, the error is
+    /// returned afterwards.
+    pub async fn close(&self) -> RbkResult<()> {
+        let mut result = Ok(());
+        if let Some(auto_lock) = &self.auto_lock {
+            let mut held = auto_lock.held.lock().await;
+            if held.take().is_some() {
+                result = self
+                    .request(
+                        crate::api::UnlockControlRequest::new(),
+                        DEFAULT_REQUEST_TIMEOUT,
+                    )
+                    .await
+                    .and_then(|status| status.into_result());
+            }
+        }
+
+        for client in self.port_clients() {
+            client.reset().await;
+        }
+
+        result
+    }
+
+    /// Configure the push port and remember the accepted configuration
+    ///
+    /// The robot offers no API to read its push configuration back, so the
+    /// client keeps the last one it acknowledged, see
+    /// [`RbkClient::push_config`]. A configuration rejected by the robot
+    /// leaves the remembered one untouched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{PushConfig, RbkClient};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let config = PushConfig::new(19301).with_apis([1004, 1007]);
+    /// client
+    ///     .configure_push(config, Duration::from_secs(5))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn configure_push(
+        &self,
+        config: crate::api::PushConfig,
+        timeout: Duration,
+    ) -> RbkResult<()> {
+        self.request(config.clone().into_request(), timeout)
+            .await?
+            .into_result()?;
+
+        *self.push_config.lock().unwrap() = Some(config);
+        Ok(())
+    }
+
+    /// Push configuration last acknowledged through
+    /// [`RbkClient::configure_push`]
+    ///
+    /// Returns `None` until a configuration was accepted. The robot forgets
+    /// its push configuration when it reboots, so a supervisor can compare
+    /// this against what it expects to receive and re-subscribe after a
+    /// reconnect.
+    pub fn push_config(&self) -> Option<crate::api::PushConfig> {
+        self.push_config.lock().unwrap().clone()
+    }
+
+    /// Send a request to the robot and keep the transport-level details
+    ///
+    /// Behaves like [`RbkClient::request`], but also returns the `flow_no`
+    /// and `api_no` echoed in the response header together with the raw
+    /// body. Useful when debugging firmware that mislabels or reorders
+    /// responses.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, BatteryStatusRequest};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let request = BatteryStatusRequest::new();
+    /// let meta = client
+    ///     .request_with_meta(request, Duration::from_secs(10))
+    ///     .await?;
+    ///
+    /// println!("flow_no={} api_no={}", meta.flow_no, meta.api_no);
+    /// println!("raw body: {}", meta.raw_body);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_with_meta<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+    ) -> RbkResult<ResponseMeta<T::Response>>
+    where
+        T: crate::api::ToRequestBody + crate::api::FromResponseBody,
+    {
+        let timeout = request_timeout(timeout);
+
+        let api = request.to_api_request();
+        let request_str = request
+            .to_request_body()
+            .map_err(|e| RbkError::ParseError(e.to_string()))?;
+        trace!(
+            "API {} request body: {}",
+            api.api_no(),
+            loggable_body(&request_str, request.sensitive())
+        );
+
+        let frame = self
+            .port_client_for(api, timeout)
+            .await?
+            .request_frame(api.api_no(), request_str.as_bytes(), timeout)
+            .await?;
+
+        let parsed = serde_json::from_slice(&self.response_json(&frame.body))
+            .map_err(|e| {
+            RbkError::response_parse(api.api_no(), &frame.body, e)
+        })?;
+
+        Ok(ResponseMeta {
+            flow_no: frame.flow_no,
+            api_no: frame.api_no,
+            raw_body: String::from_utf8_lossy(&frame.body).into_owned(),
+            parsed,
+        })
+    }
+
+    /// Send a request with a binary body to the robot
+    ///
+    /// Unlike [`RbkClient::request`] neither the request nor the response
+    /// body is assumed to be UTF-8 JSON, which is needed for opaque
+    /// integrator data such as [`WriteTransparentDataRequest`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, TransparentData};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let request = TransparentData::new(vec![0xde, 0xad]).into_request();
+    /// client
+    ///     .request_raw(request, Duration::from_secs(10))
+    ///     .await?
+    ///     .into_result()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`WriteTransparentDataRequest`]: crate::WriteTransparentDataRequest
+    pub async fn request_raw<T>(
+        &self,
+        request: T,
+        timeout: Duration,
+    ) -> RbkResult<T::Response>
+    where
+        T: crate::api::ToRawRequestBody + crate::api::FromRawResponseBody,
+    {
+        let timeout = request_timeout(timeout);
+
+        let api = request.to_api_request();
+        let response_body = self
+            .port_client_for(api, timeout)
+            .await?
+            .request(api.api_no(), &request.to_raw_body(), timeout)
+            .await?;
+
+        T::from_raw_response(&response_body).map_err(|e| {
+            RbkError::response_parse(api.api_no(), &response_body, e)
+        })
+    }
+
+    /// Poll a single query at a fixed interval
+    ///
+    /// Returns a stream yielding a fresh response every `interval`, each
+    /// request sent with the given `timeout` (defaults to 10 seconds if
+    /// zero). Requests share the port connection with every other call on
+    /// this client. A failed request is yielded as an error and polling
+    /// continues on the next tick; polling stops once the stream is dropped.
+    /// An `interval` shorter than one millisecond is raised to one
+    /// millisecond.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use seersdk_rs::{BatteryStatusRequest, RbkClient};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let stream = client.poll(
+    ///     BatteryStatusRequest::new(),
+    ///     Duration::from_secs(1),
+    ///     Duration::from_secs(5),
+    /// );
+    /// let mut stream = std::pin::pin!(stream);
+    ///
+    /// while let Some(status) = stream.next().await {
+    ///     println!("{:?}", status);
+    /// }
+    /// # }
+    /// ```
+    pub fn poll<T>(
+        &self,
+        request: T,
+        interval: Duration,
+        timeout: Duration,
+    ) -> impl Stream<Item = RbkResult<T::Response>>
+    where
+        T: Clone + crate::api::ToRequestBody + crate::api::FromResponseBody,
+    {
+        let mut ticker = tokio::time::interval(interval.max(MIN_POLL_INTERVAL));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        futures_util::stream::unfold(
+            (ticker, request),
+            move |(mut ticker, request)| async move {
+                ticker.tick().await;
+                let result = self.request(request.clone(), timeout).await;
+                Some((result, (ticker, request)))
+            },
+        )
+    }
+
+    /// Start navigation to a target and wait until the task finishes
+    ///
+    /// Sends a [`MoveToTargetRequest`], then polls the navigation status
+    /// (API 1020) every `poll_interval` until the task reaches a terminal
+    /// state, which is returned. If `overall_timeout` elapses first
+    /// [`RbkError::Timeout`] is returned and the task keeps running, use
+    /// [`RbkClient::navigate_and_wait_or_cancel`] to stop it instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{MoveToTarget, RbkClient, TaskStatus};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let status = client
+    ///     .navigate_and_wait(
+    ///         MoveToTarget::new("LM1"),
+    ///         Duration::from_millis(500),
+    ///         Duration::from_secs(120),
+    ///     )
+    ///     .await?;
+    ///
+    /// assert_eq!(status, TaskStatus::Completed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`MoveToTargetRequest`]: crate::MoveToTargetRequest
+    pub async fn navigate_and_wait(
+        &self,
+        target: crate::api::MoveToTarget,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<crate::api::TaskStatus> {
+        let target_id = target.target.clone();
+        let wait = async {
+            self.request(
+                crate::api::MoveToTargetRequest::new(target),
+                Duration::ZERO,
+            )
+            .await?
+            .into_result()?;
+
+            self.wait_for_navigation(&target_id, poll_interval).await
+        };
+
+        tokio::time::timeout(overall_timeout, wait)
+            .await
+            .map_err(|_| RbkError::Timeout)?
+    }
+
+    /// Start navigation to a target without waiting for it to finish
+    ///
+    /// Sends a [`MoveToTargetRequest`] and returns a handle to wait for or
+    /// cancel the navigation later, e.g. from another task.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{MoveToTarget, RbkClient};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let navigation = client.start_navigation(MoveToTarget::new("LM1")).await?;
+    ///
+    /// // The operator changed their mind
+    /// navigation.cancel().await?;
+    /// let status = navigation.await_completion(Duration::from_secs(10)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`MoveToTargetRequest`]: crate::MoveToTargetRequest
+    pub async fn start_navigation(
+        &self,
+        target: crate::api::MoveToTarget,
+    ) -> RbkResult<NavigationHandle> {
+        let target_id = target.target.clone();
+        self.request(
+            crate::api::MoveToTargetRequest::new(target),
+            Duration::ZERO,
+        )
+        .await?
+        .into_result()?;
+
+        Ok(NavigationHandle {
+            client: self.clone(),
+            target: target_id,
+            poll_interval: DEFAULT_NAVIGATION_POLL_INTERVAL,
+        })
+    }
+
+    /// Poll the navigation status (API 1020) every `poll_interval` until
+    /// the navigation to `target` reaches a terminal state
+    ///
+    /// Right after a move is accepted the robot may still report the
+    /// terminal state of the previous task. A terminal state is only taken
+    /// once the navigation was seen active, or if it is reported for
+    /// `target`, which covers a move finishing between two polls.
+    async fn wait_for_navigation(
+        &self,
+        target: &str,
+        poll_interval: Duration,
+    ) -> RbkResult<crate::api::TaskStatus> {
+        let statuses = self.poll(
+            crate::api::NavStatusRequest::new(
+                crate::api::GetNavStatus::new().with_simple(true),
+            ),
+            poll_interval,
+            Duration::ZERO,
+        );
+        let mut statuses = std::pin::pin!(statuses);
+
+        let mut seen_active = false;
+        while let Some(status) = statuses.next().await {
+            let status = status?.into_result()?;
+            if status.status.is_active() {
+                seen_active = true;
+            } else if status.status.is_terminal()
+                && (seen_active || status.target_id == target)
+            {
+                return Ok(status.status);
+            }
+        }
+
+        unreachable!("navigation status stream never ends")
+    }
+
+    /// Wait until the task with the given id reaches a terminal state
+    ///
+    /// Polls the status of just this task through the task status package
+    /// (API 1110) every `poll_interval`, which is cheaper than reading the
+    /// whole navigation status. A task missing from the package, e.g.
+    /// because the robot dropped it, is reported as
+    /// [`TaskStatus::NotFound`]. If `timeout` elapses first
+    /// [`RbkError::Timeout`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, TaskStatus};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let status = client
+    ///     .wait_for_task(
+    ///         "pick_1",
+    ///         Duration::from_millis(500),
+    ///         Duration::from_secs(120),
+    ///     )
+    ///     .await?;
+    ///
+    /// assert_eq!(status, TaskStatus::Completed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`TaskStatus::NotFound`]: crate::TaskStatus::NotFound
+    pub async fn wait_for_task(
+        &self,
+        task_id: impl Into<crate::api::TaskId>,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> RbkResult<crate::api::TaskStatus> {
+        let task_id = task_id.into();
+        let wait = async {
+            let packages = self.poll(
+                crate::api::TaskStatusRequest::new(
+                    crate::api::GetTaskStatus::for_ids([task_id.as_str()]),
+                ),
+                poll_interval,
+                Duration::ZERO,
+            );
+            let mut packages = std::pin::pin!(packages);
+
+            while let Some(package) = packages.next().await {
+                let status = package?
+                    .into_result()?
+                    .tasks
+                    .into_iter()
+                    .find(|task| task.task_id == task_id)
+                    .map_or(crate::api::TaskStatus::NotFound, |task| {
+                        task.status
+                    });
+                if status.is_terminal() {
+                    return Ok(status);
+                }
+            }
+
+            unreachable!("task status stream never ends")
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| RbkError::Timeout)?
+    }
+
+    /// Same as [`RbkClient::navigate_and_wait`], but cancels the navigation
+    /// task (API 3003) when waiting for it fails once the robot accepted
+    /// the move, e.g. because `overall_timeout` elapsed or the navigation
+    /// status could not be read. The error is returned either way.
+    pub async fn navigate_and_wait_or_cancel(
+        &self,
+        target: crate::api::MoveToTarget,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<crate::api::TaskStatus> {
+        let target_id = target.target.clone();
+        let mut accepted = false;
+        let navigate = async {
+            self.request(
+                crate::api::MoveToTargetRequest::new(target),
+                Duration::ZERO,
+            )
+            .await?
+            .into_result()?;
+            accepted = true;
+
+            self.wait_for_navigation(&target_id, poll_interval).await
+        };
+        let result = tokio::time::timeout(overall_timeout, navigate)
+            .await
+            .unwrap_or(Err(RbkError::Timeout));
+
+        // A move still in flight when the time ran out may have been
+        // accepted as well
+        let started = accepted || matches!(result, Err(RbkError::Timeout));
+        if let Err(e) = &result
+            && started
+        {
+            debug!("Navigation failed ({}), cancelling task", e);
+            if let Err(e) = self
+                .request(crate::api::CancelTaskRequest::new(), Duration::ZERO)
+                .await
+            {
+                debug!("Failed to cancel navigation task: {}", e);
+            }
+        }
+
+        result
+    }
+
+    /// Charge the robot until the battery reaches the given level
+    ///
+    /// Engages the charging relay (API 6005), polls the battery status every
+    /// `poll_interval` until `battery_level >= level` and releases the relay
+    /// again, also when polling fails or `overall_timeout` elapses, in which
+    /// case [`RbkError::Timeout`] is returned. Dropping the future releases
+    /// the relay in the background. Returns the last battery status.
+    ///
+    /// `level` must lie in the range 0.0 to 1.0, otherwise
+    /// [`RbkError::InvalidArgument`] is returned without engaging the relay.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let status = client
+    ///     .charge_until(0.9, Duration::from_secs(5), Duration::from_secs(7200))
+    ///     .await?;
+    ///
+    /// println!("Charged to {:.0}%", status.battery_level * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn charge_until(
+        &self,
+        level: f64,
+        poll_interval: Duration,
+        overall_timeout: Duration,
+    ) -> RbkResult<crate::api::BatteryStatus> {
+        if !(0.0..=1.0).contains(&level) {
+            return Err(RbkError::InvalidArgument(format!(
+                "battery level {} is outside 0.0 to 1.0",
+                level
+            )));
+        }
+
+        self.request(
+            crate::api::SetChargingRelayRequest::new(
+                crate::api::SetChargingRelay::new(true),
+            ),
+            Duration::ZERO,
+        )
+        .await?
+        .into_result()?;
+        let mut relay = ChargingRelayGuard {
+            client: Some(self.clone()),
+        };
+
+        let charge = async {
+            let statuses = self.poll(
+                crate::api::BatteryStatusRequest::new(),
+                poll_interval,
+                Duration::ZERO,
+            );
+            let mut statuses = std::pin::pin!(statuses);
+
+            while let Some(status) = statuses.next().await {
+                let status = status?.into_result()?;
+                debug!("Charging, battery level {}", status.battery_level);
+                if status.battery_level >= level {
+                    return Ok(status);
+                }
+            }
+
+            unreachable!("battery status stream never ends")
+        };
+        let result = tokio::time::timeout(overall_timeout, charge)
+            .await
+            .unwrap_or(Err(RbkError::Timeout));
+
+        relay.client = None;
+        let released = release_charging_relay(self).await;
+
+        let status = result?;
+        released?;
+
+        Ok(status)
+    }
+
+    /// Limit the speed of the robot within an area to `speed` in m/s
+    ///
+    /// Sets `param` of `plugin` (API 4100) to the limit. The RBK
+    /// documentation does not name a plugin for area speed limits, so both
+    /// are taken from the robot's configuration, usually with the area id
+    /// as the param name. The limit applies until the robot reboots or its
+    /// params are reloaded. The limit of the area the robot is in is
+    /// reported in [`RobotArea::max_speed`](crate::api::RobotArea::max_speed).
+    ///
+    /// `speed` must be finite and positive, otherwise
+    /// [`RbkError::InvalidArgument`] is returned without sending anything.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::RbkClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// client
+    ///     .set_area_speed_limit("AreaSpeedLimit", "loading_dock", 0.3)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_area_speed_limit(
+        &self,
+        plugin: impl Into<String>,
+        param: impl Into<String>,
+        speed: f64,
+    ) -> RbkResult<()> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(RbkError::InvalidArgument(format!(
+                "speed limit {} is not a positive speed",
+                speed
+            )));
+        }
+
+        self.request(
+            crate::api::SetParamsRequest::new(
+                crate::api::SetParams::new().with_param(plugin, param, speed),
+            ),
+            Duration::ZERO,
+        )
+        .await?
+        .into_result()
+        .map(drop)
+    }
+
+    /// Drive a load from one station to another
+    ///
+    /// Navigates to the pickup station, raises the jack, navigates to the
+    /// dropoff station and lowers the jack. After each navigation the
+    /// navigation status (API 1020) is polled until the task finishes,
+    /// after each jack command the jack status (API 1027) until the jack
+    /// is raised in place holding the load, or lowered in place without
+    /// it. A navigation ending in any state but completed fails with
+    /// [`RbkError::NavigationFailed`], a failing jack with
+    /// [`RbkError::JackFailed`]. A step running longer than the job's step
+    /// timeout fails with [`RbkError::Timeout`], a navigation is cancelled
+    /// (API 3003) then.
+    ///
+    /// The job stops at the first failing step, the result holds the
+    /// outcome of every step which was run.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::{RbkClient, TransportJob};
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let result = client.run_transport(TransportJob::new("LM1", "LM2")).await;
+    ///
+    /// if let Some((step, e)) = result.failed_step() {
+    ///     eprintln!("Transport failed at {:?}: {}", step, e);
+    /// }
+    /// # }
+    /// ```
+    pub async fn run_transport(&self, job: TransportJob) -> TransportResult {
+        let mut steps = Vec::with_capacity(TransportStep::ALL.len());
+
+        for step in TransportStep::ALL {
+            debug!("Transport step {:?}", step);
+            let result = match step {
+                TransportStep::MoveToPickup => {
+                    self.transport_move(&job.pickup, &job).await
+                }
+                TransportStep::Load => {
+                    self.transport_jack(
+                        crate::api::LoadJackRequest::new(),
+                        crate::api::JackOperationStatus::RisingInPlace,
+                        true,
+                        &job,
+                    )
+                    .await
+                }
+                TransportStep::MoveToDropoff => {
+                    self.transport_move(&job.dropoff, &job).await
+                }
+                TransportStep::Unload => {
+                    self.transport_jack(
+                        crate::api::UnloadJackRequest::new(),
+                        crate::api::JackOperationStatus::LoweringInPlace,
+                        false,
+                        &job,
+                    )
+                    .await
+                }
+            };
+
+            let failed = result.is_err();
+            steps.push((step, result));
+            if failed {
+                break;
+            }
+        }
+
+        TransportResult { steps }
+    }
+
+    /// Navigation step of [`RbkClient::run_transport`]
+    async fn transport_move(
+        &self,
+        station: &str,
+        job: &TransportJob,
+    ) -> RbkResult<()> {
+        let status = self
+            .navigate_and_wait_or_cancel(
+                crate::api::MoveToTarget::new(station),
+                job.poll_interval,
+                job.step_timeout,
+            )
+            .await?;
+
+        match status {
+            crate::api::TaskStatus::Completed => Ok(()),
+            status => Err(RbkError::NavigationFailed { status }),
+        }
+    }
+
+    /// Jack step of [`RbkClient::run_transport`], sends the jack command
+    /// and waits until the jack reaches `target` with `has_payload` set as
+    /// given
+    ///
+    /// Any other state, including a stopped jack which has not started
+    /// moving yet, is waited out until the step timeout.
+    async fn transport_jack<T>(
+        &self,
+        request: T,
+        target: crate::api::JackOperationStatus,
+        has_payload: bool,
+        job: &TransportJob,
+    ) -> RbkResult<()>
+    where
+        T: crate::api::ToRequestBody
+            + crate::api::FromResponseBody<Response = crate::api::StatusMessage>,
+    {
+        use crate::api::JackOperationStatus;
+
+        let wait = async {
+            self.request(request, Duration::ZERO).await?.into_result()?;
+
+            let statuses = self.poll(
+                crate::api::JackStatusRequest::new(),
+                job.poll_interval,
+                Duration::ZERO,
+            );
+            let mut statuses = std::pin::pin!(statuses);
+
+            while let Some(status) = statuses.next().await {
+                let status = status?.into_result()?;
+                if status.operation == JackOperationStatus::Failed
+                    || status.error_code != 0
+                {
+                    return Err(RbkError::JackFailed {
+                        error_code: status.error_code,
+                    });
+                }
+                if status.operation == target
+                    && status.has_payload == has_payload
+                {
+                    return Ok(());
+                }
+            }
+
+            unreachable!("jack status stream never ends")
+        };
+
+        tokio::time::timeout(job.step_timeout, wait)
+            .await
+            .map_err(|_| RbkError::Timeout)?
+    }
+
+    /// Run a sequence of type-erased requests one after another
+    ///
+    /// Each step is sent with its own timeout (defaults to 10 seconds if
+    /// zero) and the response is validated against the step's response type
+    /// before being returned as raw JSON. A failing step does not abort the
+    /// sequence, the result of every step is reported in order.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::*;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let steps: Vec<(Box<dyn DynRequest>, Duration)> = vec![
+    ///     (Box::new(LoadJackRequest::new()), Duration::from_secs(5)),
+    ///     (Box::new(JackStatusRequest::new()), Duration::from_secs(1)),
+    /// ];
+    ///
+    /// for result in client.run_sequence(steps).await {
+    ///     println!("{:?}", result);
+    /// }
+    /// # }
+    /// ```
+    pub async fn run_sequence(
+        &self,
+        steps: Vec<(Box<dyn DynRequest>, Duration)>,
+    ) -> Vec<RbkResult<serde_json::Value>> {
+        let mut results = Vec::with_capacity(steps.len());
+
+        for (idx, (step, timeout)) in steps.into_iter().enumerate() {
+            let result = self.run_step(idx, step.as_ref(), timeout).await;
+            results.push(result);
+        }
+
+        results
+    }
+
+    /// Collect requests to run one after another, see [`Batch`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use seersdk_rs::*;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let client = RbkClient::new("192.168.8.114");
+    /// let results = client
+    ///     .batch()
+    ///     .push(SwitchMapRequest::new())
+    ///     .push(RelocateRequest::new())
+    ///     .push(ConfirmLocationRequest::new())
+    ///     .timeout(Duration::from_secs(5))
+    ///     .overall_timeout(Duration::from_secs(12))
+    ///     .abort_on_error(true)
+    ///     .run()
+    ///     .await;
+    ///
+    /// for result in results {
+    ///     println!("{:?}", result);
+    /// }
+    /// # }
+    /// ```
+    pub fn batch(&self) -> Batch<'_> {
+        Batch {
+            client: self,
+            steps: Vec::new(),
+            timeout: Duration::ZERO,
+            overall_timeout: None,
+            abort_on_error: false,
+        }
+    }
+
+    /// Send a type-erased request and validate the response against its
+    /// response type
+    async fn run_step(
+        &self,
+        idx: usize,
+        step: &dyn DynRequest,
+        timeout: Duration,
+    ) -> RbkResult<serde_json::Value> {
+        let timeout = request_timeout(timeout);
+        let api = step.api_request();
+
+        debug!("Sequence step {}: API {}", idx, api.api_no());
+
+        let result = async {
+            let request_str = step
+                .request_body()
+                .map_err(|e| RbkError::ParseError(e.to_string()))?;
+            trace!(
+                "Sequence step {} request body: {}",
+                idx,
+                loggable_body(&request_str, step.is_sensitive())
+            );
+            let response_body = self
+                .port_client_for(api, timeout)
+                .await?
+                .request(api.api_no(), request_str.as_bytes(), timeout)
+                .await?;
+
+            step.parse_response(&self.response_json(&response_body))
+                .map_err(|e| {
+                    RbkError::response_parse(api.api_no(), &response_body, e)
+                })
+        }
+        .await;
+
+        if let Err(ref e) = result {
+            debug!(
+                "Sequence step {} (API {}) failed: {}",
+                idx,
+                api.api_no(),
+                e
+            );
+        }
+
+        result
+    }
+
+    /// Port client serving `api`, taking the control lock first when
+    /// auto-lock is enabled and `api` is a control or navigation command,
+    /// the lock request is given `timeout`
+    async fn port_client_for(
+        &self,
+        api: ApiRequest,
+        timeout: Duration,
+    ) -> RbkResult<&RbkPortClient> {
+        if let Some(auto_lock) = &self.auto_lock
+            && matches!(port_for(&api), CONTROL_PORT | NAV_PORT)
+        {
+            self.acquire_lock(auto_lock, timeout).await?;
+        }
+
+        Ok(self.port_client(api))
+    }
+
+    async fn acquire_lock(
+        &self,
+        auto_lock: &AutoLock,
+        timeout: Duration,
+    ) -> RbkResult<()> {
+        use crate::api::ToRequestBody;
+
+        let request =
+            crate::api::LockControl::new(&auto_lock.nick_name).into_request();
+        let api = request.to_api_request();
+        let client = self.port_client(api);
+
+        let mut held = auto_lock.held.lock().await;
+        if held.is_some() && *held == client.connection_id().await {
+            return Ok(());
+        }
+
+        let body = request
+            .to_request_body()
+            .map_err(|e| RbkError::ParseError(e.to_string()))?;
+        let response_body = client
+            .request(api.api_no(), body.as_bytes(), timeout)
+            .await?;
+        let status: crate::api::StatusMessage =
+            serde_json::from_slice(&self.response_json(&response_body))
+                .map_err(|e| {
+                    RbkError::response_parse(api.api_no(), &response_body, e)
+                })?;
+        if status.code != crate::api::StatusCode::Success {
+            return Err(RbkError::LockUnavailable {
+                code: status.code,
+                message: status.message,
+            });
+        }
+
+        debug!("Took the control lock as {}", auto_lock.nick_name);
+        *held = client.connection_id().await;
+        Ok(())
+    }
+
+    fn port_client(&self, api: ApiRequest) -> &RbkPortClient {
+        let port = port_for(&api);
+        self.port_clients()
+            .into_iter()
+            .find(|client| client.port() == port)
+            .expect("every routed port has a port client")
+    }
+}
+
+/// Requests run one after another, created by [`RbkClient::batch`]
+///
+/// Steps are sent in the order they were added, each waiting for the
+/// previous one to finish, as multi-step workflows such as locking control
+/// before switching the map depend on it. Unlike
+/// [`RbkClient::run_sequence`], a step the robot answers with a non-zero
+/// `ret_code` results in [`RbkError::BadResponse`].
+pub struct Batch<'a> {
+    client: &'a RbkClient,
+    steps: Vec<Box<dyn DynRequest>>,
+    timeout: Duration,
+    overall_timeout: Option<Duration>,
+    abort_on_error: bool,
+}
+
+impl Batch<'_> {
+    /// Append a request to the batch
+    pub fn push<T>(mut self, request: T) -> Self
+    where
+        T: DynRequest + 'static,
+    {
+        self.steps.push(Box::new(request));
+        self
+    }
+
+    /// Response timeout of every step, 10 seconds by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Time the whole batch is given, unlimited by default
+    ///
+    /// Once it elapses the step in flight fails with
+    /// [`RbkError::Timeout`] and the steps after it are not sent.
+    pub fn overall_timeout(mut self, timeout: Duration) -> Self {
+        self.overall_timeout = Some(timeout);
+        self
+    }
+
+    /// Stop at the first failing step, the steps after it are not sent and
+    /// have no result. Disabled by default.
+    pub fn abort_on_error(mut self, abort: bool) -> Self {
+        self.abort_on_error = abort;
+        self
+    }
+
+    /// Run the steps, returning the result of every step that was sent in
+    /// order
+    pub async fn run(self) -> Vec<RbkResult<serde_json::Value>> {
+        let mut results = Vec::with_capacity(self.steps.len());
+        let deadline = self
+            .overall_timeout
+            .map(|timeout| tokio::time::Instant::now() + timeout);
+
+        for (idx, step) in self.steps.iter().enumerate() {
+            let api_no = step.api_request().api_no();
+            let run = async {
+                self.client
+                    .run_step(idx, step.as_ref(), self.timeout)
+                    .await
+                    .and_then(|value| check_ret_code(api_no, value))
+            };
+            let result = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, run)
+                    .await
+                    .unwrap_or(Err(RbkError::Timeout)),
+                None => run.await,
+            };
+            let timed_out = matches!(result, Err(RbkError::Timeout));
+            let failed = result.is_err();
+            results.push(result);
+
+            if timed_out && deadline.is_some() {
+                debug!("Batch ran out of time at step {}", idx);
+                break;
             }
-            ApiRequest::Kernel(_) => {
-                self.kernel_client
-                    .request(api_no, &request_str, timeout)
-                    .await?
+            if failed && self.abort_on_error {
+                debug!("Batch aborted at step {}", idx);
+                break;
             }
-            ApiRequest::Push(_) => {
-                self.misc_client
-                    .request(api_no, &request_str, timeout)
-                    .await?
+        }
+
+        results
+    }
+}
+
+/// Turn a raw response of the given API reporting an error code into an
+/// error
+fn check_ret_code(
+    api_no: u16,
+    value: serde_json::Value,
+) -> RbkResult<serde_json::Value> {
+    use serde::Deserialize;
+
+    crate::api::StatusMessage::deserialize(&value)
+        .map_err(|e| {
+            RbkError::response_parse(api_no, value.to_string().as_bytes(), e)
+        })?
+        .into_result()?;
+    Ok(value)
+}
+
+/// JSON part of a response body
+///
+/// Some firmware prepends a UTF-8 BOM or pads the body with NUL bytes up
+/// to the declared length, both of which the JSON parser rejects. Buggy
+/// firmware also concatenates two objects in one body, only the first one
+/// is kept then.
+fn json_body(body: &[u8]) -> &[u8] {
+    let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(body);
+    let end = body
+        .iter()
+        .rposition(|b| *b != 0 && !b.is_ascii_whitespace())
+        .map_or(0, |last| last + 1);
+    let body = &body[..end];
+
+    // Malformed bodies are left to the caller's parser to report
+    let mut values = serde_json::Deserializer::from_slice(body)
+        .into_iter::<serde::de::IgnoredAny>();
+    match values.next() {
+        Some(Ok(_)) if values.byte_offset() < body.len() => {
+            let first = values.byte_offset();
+            warn!(
+                "Ignoring {} bytes trailing the JSON response body",
+                body.len() - first
+            );
+            &body[..first]
+        }
+        _ => body,
+    }
+}
+
+/// Probe every one of `hosts` on `port`, see [`RbkClient::discover`]
+async fn discover_on_port(
+    hosts: impl IntoIterator<Item = Ipv4Addr, IntoIter: Send>,
+    port: u16,
+    timeout: Duration,
+) -> Vec<DiscoveredRobot> {
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    let mut probes = futures_util::stream::iter(hosts)
+        .map(|host| async move {
+            if tokio::time::Instant::now() >= deadline {
+                return None;
             }
-        };
 
-        serde_json::from_str(&response_str)
-            .map_err(|e| RbkError::ParseError(e.to_string()))
+            let options = PortOptions {
+                connect_timeout: timeout,
+                connect_attempts: 1,
+                ..PortOptions::default()
+            };
+            let client =
+                RbkPortClient::with_options(host.to_string(), port, options);
+            let result = tokio::time::timeout_at(
+                deadline,
+                client.request(StateApi::Info as u16, &[], timeout),
+            )
+            .await;
+            // The probe's read task lives on until the connection is closed
+            client.reset().await;
+
+            let body = result.ok()?.ok()?;
+            let info: crate::api::CommonInfo =
+                serde_json::from_slice(json_body(&body)).ok()?;
+
+            Some(DiscoveredRobot {
+                host,
+                id: info.id,
+                model: info.model,
+                version: info.version,
+            })
+        })
+        .buffer_unordered(DISCOVERY_CONCURRENCY);
+
+    // Every probe gives up at the deadline, so they all get to close their
+    // connection
+    let mut robots = Vec::new();
+    while let Some(probe) = probes.next().await {
+        if let Some(robot) = probe {
+            debug!("Discovered robot {} at {}", robot.id, robot.host);
+            robots.push(robot);
+        }
+    }
+
+    robots
+}
+
+/// Run a command of [`RbkClient::emergency_stop_all`], bounded by
+/// [`EMERGENCY_STOP_TIMEOUT`] from the start including connecting
+async fn stop_command(
+    request: impl Future<Output = RbkResult<crate::api::StatusMessage>>,
+) -> RbkResult<()> {
+    tokio::time::timeout(EMERGENCY_STOP_TIMEOUT, request)
+        .await
+        .map_err(|_| RbkError::Timeout)??
+        .into_result()
+}
+
+/// Time a robot information query on the given port, the content of the
+/// answer is not checked
+async fn ping_port(
+    client: &RbkPortClient,
+    timeout: Duration,
+) -> RbkResult<Duration> {
+    client.ensure_connected().await?;
+
+    let started = tokio::time::Instant::now();
+    client.request(StateApi::Info as u16, &[], timeout).await?;
+    Ok(started.elapsed())
+}
+
+/// Request body as it may appear in logs
+fn loggable_body(body: &str, sensitive: bool) -> Cow<'_, str> {
+    if sensitive {
+        Cow::Owned(format!("<redacted {} bytes>", body.len()))
+    } else {
+        Cow::Borrowed(body)
+    }
+}
+
+/// Port serving the given API
+///
+/// This is the single place deciding the routing, the API number ranges in
+/// the docs only describe it:
+/// - State (1000-1999): 19204
+/// - Control (2000-2999): 19205
+/// - Navigation (3000-3999): 19206
+/// - Config (4000-4999): 19207
+/// - Kernel (5000-5999): 19208
+/// - Peripheral (6000-6998) and Push (9000+): 19210
+pub(crate) fn port_for(api: &ApiRequest) -> u16 {
+    match api {
+        ApiRequest::State(_) => STATE_PORT,
+        ApiRequest::Control(_) => CONTROL_PORT,
+        ApiRequest::Nav(_) => NAV_PORT,
+        ApiRequest::Config(_) => CONFIG_PORT,
+        ApiRequest::Kernel(_) => KERNEL_PORT,
+        ApiRequest::Peripheral(_) => MISC_PORT,
+        ApiRequest::Push(_) => MISC_PORT,
+        ApiRequest::Custom(api_no) => match api_no {
+            1000..=1999 => STATE_PORT,
+            2000..=2999 => CONTROL_PORT,
+            3000..=3999 => NAV_PORT,
+            4000..=4999 => CONFIG_PORT,
+            5000..=5999 => KERNEL_PORT,
+            _ => MISC_PORT,
+        },
+    }
+}
+
+/// Disengage the charging relay (API 6005)
+async fn release_charging_relay(client: &RbkClient) -> RbkResult<()> {
+    client
+        .request(
+            crate::api::SetChargingRelayRequest::new(
+                crate::api::SetChargingRelay::new(false),
+            ),
+            Duration::ZERO,
+        )
+        .await?
+        .into_result()
+        .map(drop)
+}
+
+/// Releases the charging relay engaged by [`RbkClient::charge_until`] when
+/// it is dropped before finishing
+struct ChargingRelayGuard {
+    client: Option<RbkClient>,
+}
+
+impl Drop for ChargingRelayGuard {
+    fn drop(&mut self) {
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        // Without a runtime there is nothing to send the release on
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                if let Err(e) = release_charging_relay(&client).await {
+                    warn!("Failed to release the charging relay: {}", e);
+                }
+            });
+        }
     }
 }
 
 impl Drop for RbkClient {
     fn drop(&mut self) {
         // Note: Drop cannot be async in Rust, and proper cleanup of TCP connections
-        // requires async operations. The connections will be closed when the underlying
-        // RbkPortClient instances are dropped, which will abort their read tasks.
-        // For graceful shutdown with proper connection cleanup, users should manage
-        // the client lifetime explicitly within an async context.
+        // requires async operations. Dropping the port clients does not close the
+        // connections: each read task is detached, not aborted, and keeps its
+        // connection open until the robot closes it. Call close() from an async
+        // context to shut the connections down.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{
+        ConfigApi, ControlApi, KernelApi, NavApi, PeripheralApi, PushApi,
+    };
+
+    /// Port an API number is documented to be served on
+    fn documented_port(api_no: u16) -> u16 {
+        match api_no {
+            1000..=1999 => STATE_PORT,
+            2000..=2999 => CONTROL_PORT,
+            3000..=3999 => NAV_PORT,
+            4000..=4999 => CONFIG_PORT,
+            5000..=5999 => KERNEL_PORT,
+            6000..=6998 => MISC_PORT,
+            9000.. => MISC_PORT,
+            _ => panic!("API {} is outside every documented range", api_no),
+        }
+    }
+
+    #[test]
+    fn test_api_routing_matches_documented_ranges() {
+        let apis = [
+            ApiRequest::State(StateApi::Info),
+            ApiRequest::State(StateApi::Laser),
+            ApiRequest::State(StateApi::Nav),
+            ApiRequest::State(StateApi::TaskPackage),
+            ApiRequest::State(StateApi::Alarm),
+            ApiRequest::Control(ControlApi::Stop),
+            ApiRequest::Control(ControlApi::ClearWeightdevvalue),
+            ApiRequest::Nav(NavApi::Pause),
+            ApiRequest::Nav(NavApi::MoveToTarget),
+            ApiRequest::Nav(NavApi::TaskListList),
+            ApiRequest::Config(ConfigApi::Lock),
+            ApiRequest::Config(ConfigApi::Push),
+            ApiRequest::Config(ConfigApi::ClearWarning),
+            ApiRequest::Kernel(KernelApi::Shutdown),
+            ApiRequest::Kernel(KernelApi::Reboot),
+            ApiRequest::Kernel(KernelApi::ResetFirmware),
+            ApiRequest::Peripheral(PeripheralApi::SetChargingRelay),
+            ApiRequest::Peripheral(PeripheralApi::SetModbus),
+            ApiRequest::Peripheral(PeripheralApi::Replay),
+            ApiRequest::Push(PushApi::Config),
+            ApiRequest::Custom(1999),
+            ApiRequest::Custom(2100),
+            ApiRequest::Custom(3999),
+            ApiRequest::Custom(4500),
+            ApiRequest::Custom(5001),
+            ApiRequest::Custom(6500),
+            ApiRequest::Custom(9300),
+        ];
+
+        let client = RbkClient::new("localhost");
+        for api in apis {
+            assert_eq!(port_for(&api), client.port_client(api).port());
+            assert_eq!(
+                port_for(&api),
+                documented_port(api.api_no()),
+                "{:?} (API {}) is routed to the wrong port",
+                api,
+                api.api_no()
+            );
+        }
+    }
+
+    #[test]
+    fn test_port_for_each_module() {
+        assert_eq!(port_for(&ApiRequest::State(StateApi::Info)), 19204);
+        assert_eq!(port_for(&ApiRequest::Control(ControlApi::Stop)), 19205);
+        assert_eq!(port_for(&ApiRequest::Nav(NavApi::Cancel)), 19206);
+        assert_eq!(port_for(&ApiRequest::Config(ConfigApi::Lock)), 19207);
+        assert_eq!(port_for(&ApiRequest::Kernel(KernelApi::Reboot)), 19208);
+        assert_eq!(
+            port_for(&ApiRequest::Peripheral(PeripheralApi::SetModbus)),
+            19210
+        );
+        assert_eq!(port_for(&ApiRequest::Push(PushApi::Config)), 19210);
+    }
+
+    #[tokio::test]
+    async fn test_discover() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // Two robots on loopback addresses sharing one free port
+        let first = TcpListener::bind("127.0.0.2:0").await.unwrap();
+        let port = first.local_addr().unwrap().port();
+        let second = TcpListener::bind(("127.0.0.3", port)).await.unwrap();
+        let closed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        for (listener, id) in [(first, "robot-a"), (second, "robot-b")] {
+            let closed = closed.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let closed = closed.clone();
+                    tokio::spawn(async move {
+                        let mut header = [0u8; 16];
+                        while stream.read_exact(&mut header).await.is_ok() {
+                            let flow_no =
+                                u16::from_be_bytes([header[2], header[3]]);
+                            let body = format!(
+                                r#"{{"id":"{}","model":"AMB-150","version":"3.4.6","ret_code":0}}"#,
+                                id
+                            );
+                            let response = crate::protocol::encode_request(
+                                11000,
+                                body.as_bytes(),
+                                flow_no,
+                                crate::protocol::RESERVED,
+                            );
+                            stream.write_all(&response).await.unwrap();
+                        }
+                        closed
+                            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    });
+                }
+            });
+        }
+
+        let hosts = (1..=6).map(|host| Ipv4Addr::new(127, 0, 0, host));
+        let mut robots =
+            discover_on_port(hosts, port, Duration::from_secs(2)).await;
+        robots.sort_by_key(|robot| robot.host);
+
+        assert_eq!(robots.len(), 2, "{:?}", robots);
+        assert_eq!(robots[0].host, Ipv4Addr::new(127, 0, 0, 2));
+        assert_eq!(robots[0].id, "robot-a");
+        assert_eq!(robots[1].host, Ipv4Addr::new(127, 0, 0, 3));
+        assert_eq!(robots[1].id, "robot-b");
+        assert_eq!(robots[1].model, "AMB-150");
+
+        // The probes hang up once they got their answer
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while closed.load(std::sync::atomic::Ordering::SeqCst) < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Probe connections were left open");
+    }
+
+    #[tokio::test]
+    async fn test_request_by_past_deadline_fails_fast() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let dials = Arc::new(AtomicUsize::new(0));
+        let counter = dials.clone();
+        let client = RbkClient::builder("unused")
+            .transport(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let (client_end, _robot_end) = tokio::io::duplex(64);
+                    Ok(Box::new(client_end) as Box<dyn Transport>)
+                }
+            })
+            .build();
+
+        let started = std::time::Instant::now();
+        let result = client
+            .request_by(crate::api::BatteryStatusRequest::new(), started)
+            .await;
+        assert!(matches!(result, Err(RbkError::Timeout)), "{:?}", result);
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert_eq!(dials.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_by_deadline_covers_connect() {
+        let client = RbkClient::builder("unused")
+            .connect_timeout(Duration::from_secs(30))
+            .transport(|_| std::future::pending())
+            .build();
+
+        let started = std::time::Instant::now();
+        let result = client
+            .request_by(
+                crate::api::BatteryStatusRequest::new(),
+                started + Duration::from_millis(100),
+            )
+            .await;
+        assert!(matches!(result, Err(RbkError::Timeout)), "{:?}", result);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_json_body_strips_bom_and_padding() {
+        assert_eq!(json_body(b"\xEF\xBB\xBF{\"a\":1}"), b"{\"a\":1}");
+        assert_eq!(json_body(b"{\"a\":1}\0\0\0"), b"{\"a\":1}");
+        assert_eq!(json_body(b"{\"a\":1} \r\n\0 "), b"{\"a\":1}");
+        assert_eq!(json_body(b"\0\0"), b"");
+        assert_eq!(json_body(b"{}"), b"{}");
+        assert_eq!(json_body(b"{\"a\":1}{\"a\":2}"), b"{\"a\":1}");
+        assert_eq!(json_body(b"{\"a\":1} {\"b\"\0"), b"{\"a\":1}");
+        assert_eq!(json_body(b"{\"a\":"), b"{\"a\":");
+    }
+
+    #[tokio::test]
+    async fn test_nested_status_header() {
+        let client = |nested| {
+            let (builder, _) = scripted_builder(|_| {
+                Some(
+                    r#"{"header":{"ret_code":40001,"err_msg":"busy"},"data":{}}"#
+                        .to_string(),
+                )
+            });
+            builder.nested_status_header(nested).build()
+        };
+
+        let response = client(true)
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.code, crate::api::StatusCode::ParamMissing);
+        assert_eq!(response.message, "busy");
+
+        // Without opting in the header is ignored like any unknown field
+        let response = client(false)
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.code, crate::api::StatusCode::Success);
+    }
+
+    #[tokio::test]
+    async fn test_max_body_size() {
+        // Answer the speed query with a body over the limit
+        let (builder, _) = scripted_builder(|api_no| {
+            Some(if api_no == 1005 {
+                format!(r#"{{"ret_code":0,"err_msg":"{}"}}"#, "Z".repeat(100))
+            } else {
+                r#"{"ret_code":0,"x":1.0,"y":2.0,"angle":0.0,"confidence":1.0}"#
+                    .to_string()
+            })
+        });
+        let client = builder.max_body_size(64).build();
+
+        let started = std::time::Instant::now();
+        let result = client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(5),
+            )
+            .await;
+        assert!(
+            matches!(result, Err(RbkError::ProtocolError(_))),
+            "{:?}",
+            result
+        );
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // Responses within the limit still get through
+        client
+            .request(
+                crate::api::RobotPoseRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reserved_header_bytes() {
+        let (headers_tx, mut headers) = tokio::sync::mpsc::unbounded_channel();
+        let client = RbkClient::builder("unused")
+            .reserved(*b"rbk-01")
+            .transport(stub_transport(move |request| {
+                let _ = headers_tx.send(request.reserved);
+                Some("{}".to_string())
+            }))
+            .build();
+
+        client
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        client
+            .send_no_reply(crate::api::StopExerciseRequest::new())
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(&headers.recv().await.unwrap(), b"rbk-01");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_padded_response_bodies() {
+        // Answer the speed query with a BOM, stopping with two
+        // concatenated objects, everything else with NUL padding
+        let (client, _) = scripted_client(|api_no| {
+            Some(
+                match api_no {
+                    1005 => "\u{feff}{\"ret_code\":0,\"err_msg\":\"bom\"}",
+                    2000 => "{\"ret_code\":0,\"err_msg\":\"first\"}{\"ret_code\":40001,\"err_msg\":\"second\"}",
+                    _ => "{\"ret_code\":0,\"err_msg\":\"nul\"}\0\0\0\0",
+                }
+                .to_string(),
+            )
+        });
+
+        let response = client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.message, "bom");
+
+        let meta = client
+            .request_with_meta(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(meta.parsed.message, "nul");
+
+        let response = client
+            .request(
+                crate::api::StopExerciseRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.message, "first");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_transport() {
+        // Answer every request with the port it arrived on
+        let client = RbkClient::builder("unused")
+            .transport(stub_transport(|request| {
+                Some(format!(
+                    r#"{{"ret_code":0,"err_msg":"{}"}}"#,
+                    request.port
+                ))
+            }))
+            .build();
+
+        let response = client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.message, STATE_PORT.to_string());
+
+        let response = client
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.message, NAV_PORT.to_string());
+    }
+
+    /// Request received by the robot behind [`stub_transport`]
+    struct StubRequest {
+        port: u16,
+        api_no: u16,
+        reserved: [u8; 6],
+    }
+
+    type StubConnect = std::future::Ready<std::io::Result<Box<dyn Transport>>>;
+
+    /// Transport factory connecting to an in-memory robot which answers
+    /// each request with the body `answer` returns for it, or never for
+    /// `None`
+    fn stub_transport(
+        answer: impl Fn(&StubRequest) -> Option<String> + Send + Sync + 'static,
+    ) -> impl Fn(u16) -> StubConnect + Send + Sync + 'static {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let answer = Arc::new(answer);
+        move |port| {
+            let answer = answer.clone();
+            let (client_end, mut robot_end) = tokio::io::duplex(4096);
+            tokio::spawn(async move {
+                let mut header = [0u8; 16];
+                while robot_end.read_exact(&mut header).await.is_ok() {
+                    let flow_no = u16::from_be_bytes([header[2], header[3]]);
+                    let len = u32::from_be_bytes([
+                        header[4], header[5], header[6], header[7],
+                    ]);
+                    let mut body = vec![0u8; len as usize];
+                    if robot_end.read_exact(&mut body).await.is_err() {
+                        break;
+                    }
+
+                    let request = StubRequest {
+                        port,
+                        api_no: u16::from_be_bytes([header[8], header[9]]),
+                        reserved: header[10..].try_into().unwrap(),
+                    };
+                    let Some(body) = answer(&request) else {
+                        continue;
+                    };
+                    let response = crate::protocol::encode_request(
+                        request.api_no + 10000,
+                        body.as_bytes(),
+                        flow_no,
+                        crate::protocol::RESERVED,
+                    );
+                    if robot_end.write_all(&response).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            std::future::ready(Ok(Box::new(client_end) as Box<dyn Transport>))
+        }
+    }
+
+    /// Builder of a client whose robot answers each request with the body
+    /// `answer` returns for its API number, or never for `None`, recording
+    /// the API number of every request it receives
+    fn scripted_builder(
+        answer: impl Fn(u16) -> Option<String> + Send + Sync + 'static,
+    ) -> (RbkClientBuilder, Arc<std::sync::Mutex<Vec<u16>>>) {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let builder = RbkClient::builder("unused").transport(stub_transport(
+            move |request| {
+                log.lock().unwrap().push(request.api_no);
+                answer(request.api_no)
+            },
+        ));
+
+        (builder, received)
+    }
+
+    /// Client built by [`scripted_builder`] with the default options
+    fn scripted_client(
+        answer: impl Fn(u16) -> Option<String> + Send + Sync + 'static,
+    ) -> (RbkClient, Arc<std::sync::Mutex<Vec<u16>>>) {
+        let (builder, received) = scripted_builder(answer);
+        (builder.build(), received)
+    }
+
+    #[tokio::test]
+    async fn test_batch_overall_timeout() {
+        // The robot never answers IMU queries
+        let (client, received) = scripted_client(|api_no| {
+            (api_no != 1014).then(|| r#"{"ret_code":0}"#.to_string())
+        });
+
+        let started = std::time::Instant::now();
+        let results = client
+            .batch()
+            .push(crate::api::RobotSpeedRequest::new())
+            .push(crate::api::RobotImuRequest::new())
+            .push(crate::api::RobotPoseRequest::new())
+            .timeout(Duration::from_secs(10))
+            .overall_timeout(Duration::from_millis(200))
+            .run()
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(results.len(), 2, "Steps after the timeout were sent");
+        assert!(results[0].is_ok(), "{:?}", results[0]);
+        assert!(
+            matches!(results[1], Err(RbkError::Timeout)),
+            "{:?}",
+            results[1]
+        );
+        assert!(!received.lock().unwrap().contains(&1004));
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_bundle_survives_stalled_subsystem() {
+        // The robot never answers IMU queries
+        let (client, _) = scripted_client(|api_no| {
+            (api_no != 1014).then(|| r#"{"ret_code":0}"#.to_string())
+        });
+
+        let bundle =
+            client.diagnostics_bundle(Duration::from_millis(200)).await;
+
+        assert!(bundle.imu.is_none());
+        assert!(bundle.encoders.is_some());
+        assert!(bundle.motors.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ping_all_counts_error_replies() {
+        let (client, _) = scripted_client(|_| {
+            Some(r#"{"ret_code":60000,"err_msg":"unsupported"}"#.to_string())
+        });
+
+        let rtts = client.ping_all(Duration::from_secs(1)).await;
+
+        assert_eq!(rtts.len(), 6);
+        for (port, rtt) in rtts {
+            assert!(rtt.is_ok(), "port {}: {:?}", port, rtt);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_navigation_ignores_previous_task_status() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The previous task's completion is still reported right after the
+        // move is accepted
+        let polls = AtomicUsize::new(0);
+        let (client, received) = scripted_client(move |api_no| {
+            if api_no != 1020 {
+                return Some(r#"{"ret_code":0}"#.to_string());
+            }
+            let (status, target) = match polls.fetch_add(1, Ordering::SeqCst) {
+                0 => (4, "LM0"),
+                1 | 2 => (2, "LM1"),
+                _ => (4, "LM1"),
+            };
+            Some(format!(
+                r#"{{"task_status":{status},"target_id":"{target}","ret_code":0}}"#
+            ))
+        });
+
+        let status = client
+            .navigate_and_wait(
+                crate::api::MoveToTarget::new("LM1"),
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(status, crate::api::TaskStatus::Completed);
+        let received = received.lock().unwrap();
+        assert_eq!(received.iter().filter(|&&api| api == 1020).count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_navigation_canceled_when_status_fails() {
+        // The navigation status lacks `task_status` and fails to parse
+        let (client, received) =
+            scripted_client(|_| Some(r#"{"ret_code":0}"#.to_string()));
+
+        let result = client
+            .navigate_and_wait_or_cancel(
+                crate::api::MoveToTarget::new("LM1"),
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(RbkError::ResponseParseError { .. })),
+            "{:?}",
+            result
+        );
+        assert!(received.lock().unwrap().contains(&3003));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_navigation_not_canceled() {
+        let (client, received) =
+            scripted_client(|_| Some(r#"{"ret_code":40000}"#.to_string()));
+
+        let result = client
+            .navigate_and_wait_or_cancel(
+                crate::api::MoveToTarget::new("LM1"),
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(RbkError::BadResponse { .. })),
+            "{:?}",
+            result
+        );
+        assert_eq!(*received.lock().unwrap(), [3051]);
+    }
+
+    #[tokio::test]
+    async fn test_transport_jack_waits_for_target_state() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The jack still reports stopped right after the command, then
+        // rises for a while before it is in place holding the load
+        let polls = AtomicUsize::new(0);
+        let (client, received) = scripted_client(move |api_no| {
+            if api_no != 1027 {
+                return Some(r#"{"ret_code":0}"#.to_string());
+            }
+            let (state, full) = match polls.fetch_add(1, Ordering::SeqCst) {
+                0 => (4, false),
+                1 | 2 => (0, false),
+                _ => (1, true),
+            };
+            Some(format!(
+                r#"{{"jack_mode":true,"jack_enable":true,"jack_error_code":0,
+                "jack_state":{state},"jack_isFull":{full},"jack_speed":0,
+                "jack_emc":false,"jack_height":0.0,"ret_code":0}}"#
+            ))
+        });
+
+        let job = TransportJob::new("LM1", "LM2")
+            .poll_interval(Duration::from_millis(10))
+            .step_timeout(Duration::from_secs(5));
+        client
+            .transport_jack(
+                crate::api::LoadJackRequest::new(),
+                crate::api::JackOperationStatus::RisingInPlace,
+                true,
+                &job,
+            )
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0], 6070);
+        assert_eq!(received.iter().filter(|&&api| api == 1027).count(), 4);
+    }
+
+    /// Client whose robot answers 4005 (lock) with `lock_code`, or never
+    /// for `None`, and every other request with success, counting the
+    /// lock requests
+    fn auto_lock_client(
+        lock_code: Option<u32>,
+    ) -> (RbkClient, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let locks = Arc::new(AtomicUsize::new(0));
+        let counter = locks.clone();
+        let (builder, _) = scripted_builder(move |api_no| {
+            let code = if api_no == 4005 {
+                counter.fetch_add(1, Ordering::SeqCst);
+                lock_code?
+            } else {
+                0
+            };
+            Some(format!(r#"{{"ret_code":{}}}"#, code))
+        });
+
+        (builder.auto_lock("tester").build(), locks)
+    }
+
+    #[tokio::test]
+    async fn test_auto_lock_taken_once_for_control_requests() {
+        use std::sync::atomic::Ordering;
+
+        let (client, locks) = auto_lock_client(Some(0));
+        let timeout = Duration::from_secs(1);
+
+        client
+            .request(crate::api::RobotSpeedRequest::new(), timeout)
+            .await
+            .unwrap();
+        assert_eq!(locks.load(Ordering::SeqCst), 0, "state APIs need no lock");
+
+        for _ in 0..2 {
+            client
+                .request(crate::api::CancelTaskRequest::new(), timeout)
+                .await
+                .unwrap();
+        }
+        assert_eq!(locks.load(Ordering::SeqCst), 1);
+
+        // Closing releases the lock, the next command takes it again
+        client.close().await.unwrap();
+        client
+            .request(crate::api::CancelTaskRequest::new(), timeout)
+            .await
+            .unwrap();
+        assert_eq!(locks.load(Ordering::SeqCst), 2);
+
+        // The lock is taken again once its connection is gone
+        client.config_client.reset().await;
+        client
+            .request(crate::api::CancelTaskRequest::new(), timeout)
+            .await
+            .unwrap();
+        assert_eq!(locks.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_auto_lock_uses_request_timeout() {
+        let (client, _) = auto_lock_client(None);
+
+        let started = tokio::time::Instant::now();
+        let result = client
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_millis(100),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(RbkError::ResponseTimeout { api_no: 4005 })),
+            "{:?}",
+            result
+        );
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_auto_lock_robot_busy() {
+        let (client, _) = auto_lock_client(Some(40102));
+
+        let result = client
+            .request(
+                crate::api::CancelTaskRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(RbkError::LockUnavailable {
+                    code: crate::api::StatusCode::RobotBusy,
+                    ..
+                })
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emergency_stop_all_skips_auto_lock() {
+        use std::sync::atomic::Ordering;
+
+        // Another client holds the lock
+        let (client, locks) = auto_lock_client(Some(40102));
+
+        let results = client.emergency_stop_all().await;
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(Result::is_ok), "{:?}", results);
+        assert_eq!(locks.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_cancelled_by_token() {
+        let client = RbkClient::builder("unused")
+            .transport(|_| async move {
+                // The robot never answers
+                let (client_end, robot_end) = tokio::io::duplex(4096);
+                tokio::spawn(async move {
+                    let _robot_end = robot_end;
+                    std::future::pending::<()>().await
+                });
+                Ok(Box::new(client_end) as Box<dyn Transport>)
+            })
+            .build();
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+
+        use crate::api::ToRequestBody;
+
+        let request = crate::api::RobotSpeedRequest::new();
+        let api = request.to_api_request();
+        let result = client
+            .request_cancellable(request, Duration::from_secs(10), token)
+            .await;
+        assert!(matches!(result, Err(RbkError::Cancelled)), "{:?}", result);
+
+        // Give the cleanup a chance to run if the lock was busy
+        tokio::task::yield_now().await;
+        assert_eq!(client.port_client(api).pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unsolicited_frame_is_delivered() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let client = RbkClient::builder("unused")
+            .transport(|_| async move {
+                let (client_end, mut robot_end) = tokio::io::duplex(4096);
+
+                // Push a frame nobody asked for, then answer the request
+                tokio::spawn(async move {
+                    let mut header = [0u8; 16];
+                    robot_end.read_exact(&mut header).await.unwrap();
+                    let flow_no = u16::from_be_bytes([header[2], header[3]]);
+                    let api_no = u16::from_be_bytes([header[8], header[9]]);
+
+                    let push = crate::protocol::encode_request(
+                        19301,
+                        br#"{"x":1.0}"#,
+                        flow_no.wrapping_add(100),
+                        crate::protocol::RESERVED,
+                    );
+                    robot_end.write_all(&push).await.unwrap();
+                    let response = crate::protocol::encode_request(
+                        api_no + 10000,
+                        br#"{"ret_code":0}"#,
+                        flow_no,
+                        crate::protocol::RESERVED,
+                    );
+                    robot_end.write_all(&response).await.unwrap();
+                    std::future::pending::<()>().await
+                });
+
+                Ok(Box::new(client_end) as Box<dyn Transport>)
+            })
+            .build();
+
+        let mut frames = client.unsolicited_frames();
+        client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+
+        let frame = tokio::time::timeout(Duration::from_secs(1), frames.recv())
+            .await
+            .expect("No unsolicited frame delivered")
+            .unwrap();
+        assert_eq!(frame.api_no, 19301);
+        assert_eq!(frame.body, br#"{"x":1.0}"#);
+
+        assert_eq!(client.state_client.unmatched_count().await, 1);
+        let diagnostics = client.diagnostics().await;
+        assert_eq!(diagnostics.per_port.len(), 6);
+        assert_eq!(
+            diagnostics.per_port[&STATE_PORT],
+            PortDiag {
+                unmatched_responses: 1,
+                pending_requests: 0,
+                reconnects: 0,
+                last_error: None,
+            }
+        );
+        assert_eq!(diagnostics.per_port[&NAV_PORT].unmatched_responses, 0);
+    }
+
+    #[tokio::test]
+    async fn test_connect_retries_until_port_is_up() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let connect = stub_transport(|_| Some(r#"{"ret_code":0}"#.to_string()));
+        let client = RbkClient::builder("unused")
+            .connect_attempts(3, Duration::from_millis(10))
+            .transport(move |port| {
+                // The port is not listening yet on the first attempt
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return std::future::ready(Err(
+                        std::io::ErrorKind::ConnectionRefused.into(),
+                    ));
+                }
+                connect(port)
+            })
+            .build();
+
+        let mut events = client.connection_events();
+        client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        assert!(matches!(
+            events.try_recv(),
+            Ok(ConnectionEvent::Reconnecting {
+                port: STATE_PORT,
+                attempt: 2,
+                ..
+            })
+        ));
+        assert_eq!(
+            events.try_recv(),
+            Ok(ConnectionEvent::Connected { port: STATE_PORT })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        let client = RbkClient::builder("unused")
+            .connect_timeout(Duration::from_millis(50))
+            .connect_attempts(1, Duration::ZERO)
+            // The robot never accepts the connection
+            .transport(|_| std::future::pending())
+            .build();
+
+        let result = client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_secs(10),
+            )
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(RbkError::ConnectTimeout { port: STATE_PORT })
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_response_timeout() {
+        let client = RbkClient::builder("unused")
+            .transport(|_| async move {
+                // The robot accepts the request but never answers
+                let (client_end, robot_end) = tokio::io::duplex(4096);
+                tokio::spawn(async move {
+                    let _robot_end = robot_end;
+                    std::future::pending::<()>().await
+                });
+                Ok(Box::new(client_end) as Box<dyn Transport>)
+            })
+            .build();
+
+        let result = client
+            .request(
+                crate::api::RobotSpeedRequest::new(),
+                Duration::from_millis(50),
+            )
+            .await;
+        assert!(
+            matches!(result, Err(RbkError::ResponseTimeout { api_no: 1005 })),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_sensitive_body_is_redacted() {
+        use crate::api::{CancelTaskRequest, DynRequest, ToRequestBody};
+
+        struct SecretRequest;
+
+        impl ToRequestBody for SecretRequest {
+            fn to_request_body(
+                &self,
+            ) -> Result<Cow<'static, str>, serde_json::Error> {
+                Ok(Cow::Borrowed(r#"{"password":"hunter2"}"#))
+            }
+
+            fn to_api_request(&self) -> ApiRequest {
+                ApiRequest::Config(ConfigApi::Lock)
+            }
+
+            fn sensitive(&self) -> bool {
+                true
+            }
+        }
+
+        let body = SecretRequest.to_request_body().unwrap();
+        let logged = loggable_body(&body, SecretRequest.sensitive());
+        assert_eq!(logged, "<redacted 22 bytes>");
+        assert!(!logged.contains("hunter2"));
+
+        let request = CancelTaskRequest::new();
+        assert!(!request.is_sensitive());
+        assert_eq!(loggable_body(r#"{"a":1}"#, false), r#"{"a":1}"#);
     }
 }
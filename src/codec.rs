@@ -0,0 +1,141 @@
+use crate::error::RbkError;
+use crate::frame::RbkFrame;
+use crate::protocol::{RESERVED, RbkDecoder, encode_request};
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// RBK protocol codec for `tokio_util::codec::Framed`
+///
+/// Encodes and decodes whole [`RbkFrame`]s without doing any IO, so the
+/// protocol can be driven over any byte stream, e.g. when building a
+/// custom client or a mock robot. Flow numbers are not managed, frames are
+/// written with the `flow_no` they carry.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::{SinkExt, StreamExt};
+/// use seersdk_rs::{RbkCodec, RbkFrame};
+/// use tokio::net::TcpStream;
+/// use tokio_util::codec::Framed;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let stream = TcpStream::connect("192.168.8.114:19204").await?;
+/// let mut framed = Framed::new(stream, RbkCodec::new());
+///
+/// framed
+///     .send(RbkFrame { flow_no: 1, api_no: 1007, body: Vec::new() })
+///     .await?;
+/// if let Some(frame) = framed.next().await {
+///     println!("{}", String::from_utf8_lossy(&frame?.body));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RbkCodec {
+    decoder: RbkDecoder,
+}
+
+impl RbkCodec {
+    pub fn new() -> Self {
+        Self {
+            decoder: RbkDecoder::new(),
+        }
+    }
+
+    /// Reject frames announcing a body larger than `max_body_size` bytes
+    pub fn with_max_body_size(max_body_size: usize) -> Self {
+        Self {
+            decoder: RbkDecoder::with_max_body_size(max_body_size),
+        }
+    }
+}
+
+impl Default for RbkCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for RbkCodec {
+    type Item = RbkFrame;
+    type Error = RbkError;
+
+    fn decode(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Result<Option<RbkFrame>, RbkError> {
+        self.decoder.decode(src)
+    }
+}
+
+impl Encoder<RbkFrame> for RbkCodec {
+    type Error = RbkError;
+
+    fn encode(
+        &mut self,
+        frame: RbkFrame,
+        dst: &mut BytesMut,
+    ) -> Result<(), RbkError> {
+        dst.extend_from_slice(&encode_request(
+            frame.api_no,
+            &frame.body,
+            frame.flow_no,
+            RESERVED,
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_util::codec::Framed;
+
+    #[tokio::test]
+    async fn test_framed_round_trip() {
+        let (client_end, robot_end) = tokio::io::duplex(4096);
+        let mut client = Framed::new(client_end, RbkCodec::new());
+        let mut robot = Framed::new(robot_end, RbkCodec::new());
+
+        let request = RbkFrame {
+            flow_no: 7,
+            api_no: 1007,
+            body: br#"{"simple":true}"#.to_vec(),
+        };
+        client.send(request.clone()).await.unwrap();
+
+        let received = robot.next().await.unwrap().unwrap();
+        assert_eq!(received, request);
+
+        let response = RbkFrame {
+            flow_no: received.flow_no,
+            api_no: received.api_no + 10000,
+            body: Vec::new(),
+        };
+        robot.send(response.clone()).await.unwrap();
+        assert_eq!(client.next().await.unwrap().unwrap(), response);
+    }
+
+    #[test]
+    fn test_oversized_body_rejected() {
+        let mut codec = RbkCodec::with_max_body_size(4);
+        let mut buf = BytesMut::new();
+        codec
+            .encode(
+                RbkFrame {
+                    flow_no: 1,
+                    api_no: 1000,
+                    body: vec![0; 5],
+                },
+                &mut buf,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(RbkError::ProtocolError(_))
+        ));
+    }
+}
@@ -7,18 +7,59 @@ pub enum RbkError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("Connection timeout")]
+    /// Deadline of an operation spanning several requests elapsed, such as
+    /// the overall timeout of `navigate_and_wait`
+    #[error("Operation timed out")]
     Timeout,
 
+    /// No connection could be opened within the connect timeout
+    #[error("Timed out connecting to port {port}")]
+    ConnectTimeout { port: u16 },
+
+    /// The request was sent but the robot did not answer in time
+    #[error("Timed out waiting for the response of API {api_no}")]
+    ResponseTimeout { api_no: u16 },
+
+    /// Connecting to the port failed too often in a row, no connection is
+    /// attempted until the circuit breaker cooldown has passed
+    #[error("Circuit breaker open for port {port}")]
+    CircuitOpen { port: u16 },
+
     #[error("Connection failed: {0}")]
     ConnectionFailed(String),
 
-    #[error("Write error: {0}")]
-    WriteError(String),
+    /// Opening the connection to the port failed
+    #[error("Failed to connect to port {port}: {source}")]
+    ConnectIo { port: u16, source: std::io::Error },
+
+    /// Every attempt to open a connection to the port failed, `attempts`
+    /// holds the error of each attempt in order
+    #[error(
+        "Failed to connect to port {port} after {} attempts: {}",
+        .attempts.len(),
+        format_errors(.attempts)
+    )]
+    ConnectAttemptsFailed { port: u16, attempts: Vec<RbkError> },
+
+    /// Reading from the connection failed while a request was waiting
+    /// for its response
+    #[error("Failed to read from port {port}: {source}")]
+    ReadIo { port: u16, source: std::io::Error },
+
+    /// Sending the request of the given API failed
+    #[error("Failed to write API {api_no} to port {port}: {source}")]
+    WriteIo {
+        port: u16,
+        api_no: u16,
+        source: std::io::Error,
+    },
 
     #[error("Client disposed")]
     Disposed,
 
+    #[error("Request cancelled")]
+    Cancelled,
+
     #[error("Bad API number: {0}")]
     BadApiNo(i32),
 
@@ -28,6 +69,46 @@ pub enum RbkError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    /// The message shows at most the first 256 bytes
+    /// of the body, the field holds all of it
+    #[error(
+        "Failed to parse response of API {api_no}: {source}, body: {}",
+        body_excerpt(body)
+    )]
+    ResponseParseError {
+        api_no: u16,
+        /// Response body as received, decoded lossily as UTF-8
+        body: String,
+        source: serde_json::Error,
+    },
+
+    /// Auto-lock could not take control of the robot, e.g. because
+    /// another client holds it and the robot refuses to hand it over
+    #[error(
+        "Failed to acquire the control lock: code={code:?}, message={message}"
+    )]
+    LockUnavailable { code: StatusCode, message: String },
+
+    #[error("Protocol error: {0}")]
+    ProtocolError(String),
+
+    /// An argument passed to the client was rejected before anything was
+    /// sent to the robot
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Failed to connect to ports: {}", format_port_errors(.0))]
+    PortsUnreachable(Vec<(u16, RbkError)>),
+
+    /// A navigation task ended in a state other than completed
+    #[error("Navigation ended with status {status:?}")]
+    NavigationFailed { status: crate::TaskStatus },
+
+    /// The jack stopped with a failure, `error_code` is the one reported
+    /// in the jack status
+    #[error("Jack operation failed with error code {error_code}")]
+    JackFailed { error_code: u32 },
+
     #[error(
         "Bad response from robot: code={code:?}, message={message}, timestamp={timestamp:?}"
     )]
@@ -39,3 +120,74 @@ pub enum RbkError {
 }
 
 pub type RbkResult<T> = Result<T, RbkError>;
+
+/// Bytes of a response body shown in the message of
+/// [`RbkError::ResponseParseError`]
+const MAX_DISPLAYED_BODY: usize = 256;
+
+/// Start of `body` for error messages, large bodies such as maps or
+/// scripts would flood logs otherwise
+fn body_excerpt(body: &str) -> std::borrow::Cow<'_, str> {
+    if body.len() <= MAX_DISPLAYED_BODY {
+        return body.into();
+    }
+
+    let mut end = MAX_DISPLAYED_BODY;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes)", &body[..end], body.len()).into()
+}
+
+fn format_errors(errors: &[RbkError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_port_errors(errors: &[(u16, RbkError)]) -> String {
+    errors
+        .iter()
+        .map(|(port, e)| format!("{}: {}", port, e))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl RbkError {
+    /// Error for a response body of the given API which failed to parse
+    pub(crate) fn response_parse(
+        api_no: u16,
+        body: &[u8],
+        source: serde_json::Error,
+    ) -> Self {
+        RbkError::ResponseParseError {
+            api_no,
+            body: String::from_utf8_lossy(body).into_owned(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_parse_error_truncates_body() {
+        let source = serde_json::from_str::<u32>("x").unwrap_err();
+        let body = format!("é{}", "x".repeat(1000));
+        let e = RbkError::response_parse(1300, body.as_bytes(), source);
+
+        let message = e.to_string();
+        assert!(message.ends_with(&format!("... ({} bytes)", body.len())));
+        assert!(message.len() < 400, "{}", message);
+        match e {
+            RbkError::ResponseParseError { body: full, .. } => {
+                assert_eq!(full, body)
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,10 @@
-/// Internal frame structure for RBK protocol
-#[derive(Debug, Clone)]
+/// Single RBK protocol frame, a request or a response
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RbkFrame {
+    /// Sequence number pairing a response with its request
     pub flow_no: u16,
-    #[allow(dead_code)]
+    /// API number, responses carry the request's plus 10000
     pub api_no: u16,
-    pub body: String,
+    /// Raw body, usually UTF-8 JSON
+    pub body: Vec<u8>,
 }
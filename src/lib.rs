@@ -22,15 +22,38 @@
 //! ```
 
 mod api;
+#[cfg(feature = "blocking")]
+mod blocking;
 mod client;
+#[cfg(feature = "codec")]
+mod codec;
 mod error;
 mod frame;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod port_client;
 mod protocol;
+mod transport;
 
 pub use api::*;
-pub use client::RbkClient;
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingRbkClient;
+pub use client::{
+    Batch, ConfirmResult, ConnectionEvent, Diagnostics, DiagnosticsBundle,
+    DiscoveredRobot, NavigationHandle, PortDiag, RbkClient, RbkClientBuilder,
+    ResponseMeta, RobotSnapshot, TransportJob, TransportResult, TransportStep,
+};
+#[cfg(feature = "codec")]
+pub use codec::RbkCodec;
 pub use error::{RbkError, RbkResult};
+pub use frame::RbkFrame;
+pub use transport::Transport;
+
+/// Not public API, used by exported macros
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_json;
+}
 
 #[cfg(test)]
 mod tests {
@@ -55,10 +78,20 @@ mod tests {
         // Test request without payload returns empty string
         let request = CommonInfoRequest::new();
         assert_eq!(request.to_request_body().unwrap(), "");
+        assert!(matches!(
+            request.to_request_body().unwrap(),
+            std::borrow::Cow::Borrowed(_)
+        ));
 
         // Verify all requests have proper API variants
         let api = request.to_api_request();
         assert_eq!(api.api_no(), 1000);
+        assert!(!request.sensitive());
+
+        // Script sources are kept out of logs
+        let request = UploadScript::new("a.lua", "print(1)").into_request();
+        assert!(request.sensitive());
+        assert_eq!(request.to_api_request().api_no(), 4021);
     }
 
     #[test]
@@ -0,0 +1,52 @@
+//! Metrics recorded through the `metrics` crate
+//!
+//! Emitted by every port client into whichever recorder the application
+//! installed, e.g. a Prometheus exporter:
+//!
+//! - `rbk_requests_total{api_no, outcome}`: counter of requests waiting
+//!   for a response, `outcome` is `ok`, `timeout` or `error`
+//! - `rbk_request_duration_seconds{api_no}`: histogram of the time from
+//!   sending a request until its response arrived or it failed
+//! - `rbk_reconnects_total{port}`: counter of connections opened to a port
+//!   after the first one
+
+use std::time::Duration;
+
+use crate::error::{RbkError, RbkResult};
+
+pub(crate) fn record_request<T>(
+    api_no: u16,
+    result: &RbkResult<T>,
+    elapsed: Duration,
+) {
+    let outcome = match result {
+        Ok(_) => "ok",
+        Err(
+            RbkError::ResponseTimeout { .. } | RbkError::ConnectTimeout { .. },
+        ) => "timeout",
+        Err(RbkError::ConnectAttemptsFailed { attempts, .. })
+            if matches!(
+                attempts.last(),
+                Some(RbkError::ConnectTimeout { .. })
+            ) =>
+        {
+            "timeout"
+        }
+        Err(_) => "error",
+    };
+    let api_no = api_no.to_string();
+
+    ::metrics::counter!(
+        "rbk_requests_total",
+        "api_no" => api_no.clone(),
+        "outcome" => outcome,
+    )
+    .increment(1);
+    ::metrics::histogram!("rbk_request_duration_seconds", "api_no" => api_no)
+        .record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_reconnect(port: u16) {
+    ::metrics::counter!("rbk_reconnects_total", "port" => port.to_string())
+        .increment(1);
+}
@@ -1,57 +1,286 @@
 use bytes::BytesMut;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufWriter, ReadHalf, WriteHalf};
 use tokio::net::TcpStream;
-use tokio::sync::{Mutex, Notify};
-use tracing::{debug, error};
+use tokio::sync::{Mutex, Notify, broadcast};
+use tokio::time::Instant;
+use tracing::{debug, error, warn};
 
+use crate::client::{ConnectionEvent, PortDiag};
 use crate::error::{RbkError, RbkResult};
-use crate::protocol::{RbkDecoder, encode_request};
+use crate::frame::RbkFrame;
+use crate::protocol::{
+    DEFAULT_MAX_BODY_SIZE, RESERVED, RbkDecoder, encode_request,
+};
+use crate::transport::{Connector, Transport};
+
+/// Responses kept for their requests by default
+pub(crate) const DEFAULT_MAX_BUFFERED_RESPONSES: usize = 256;
+
+/// Unsolicited frames kept for slow subscribers
+const UNSOLICITED_CAPACITY: usize = 64;
+
+/// Connection events kept for slow subscribers
+const EVENTS_CAPACITY: usize = 64;
+
+/// Time allowed for opening a connection by default
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Attempts to open a connection before giving up by default
+pub(crate) const DEFAULT_CONNECT_ATTEMPTS: u32 = 3;
+
+/// Delay before the second connection attempt by default, doubled for
+/// every further attempt
+pub(crate) const DEFAULT_CONNECT_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Settings shared by the port clients of an `RbkClient`
+#[derive(Clone)]
+pub(crate) struct PortOptions {
+    /// Opens connections instead of dialing TCP
+    pub connector: Option<Connector>,
+    /// Soft cap on responses received but not yet picked up
+    pub max_buffered_responses: usize,
+    /// Receives frames not answering a pending request
+    pub unsolicited: broadcast::Sender<RbkFrame>,
+    /// Time allowed for each attempt to open a connection
+    pub connect_timeout: Duration,
+    /// Attempts to open a connection, at least one is made
+    pub connect_attempts: u32,
+    /// Delay before the second attempt, doubled for every further one
+    pub connect_backoff: Duration,
+    /// Receives connection state changes
+    pub events: broadcast::Sender<ConnectionEvent>,
+    /// Close connections no request was sent on for this long
+    pub idle_timeout: Option<Duration>,
+    /// Stop dialing a port which keeps failing to connect
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Collect frames written within this window into a single write
+    pub write_coalescing: Option<Duration>,
+    /// Reserved header bytes stamped into every request
+    pub reserved: [u8; 6],
+    /// Largest response body accepted
+    pub max_body_size: usize,
+}
+
+/// Fail fast instead of connecting once `failures` connects in a row
+/// failed, until `cooldown` has passed
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CircuitBreaker {
+    pub failures: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for PortOptions {
+    fn default() -> Self {
+        Self {
+            connector: None,
+            max_buffered_responses: DEFAULT_MAX_BUFFERED_RESPONSES,
+            unsolicited: broadcast::channel(UNSOLICITED_CAPACITY).0,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            connect_attempts: DEFAULT_CONNECT_ATTEMPTS,
+            connect_backoff: DEFAULT_CONNECT_BACKOFF,
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+            idle_timeout: None,
+            circuit_breaker: None,
+            write_coalescing: None,
+            reserved: RESERVED,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
 
 /// Client for a specific RBK port
+///
+/// Clones share the connection and its state.
+#[derive(Clone)]
 pub(crate) struct RbkPortClient {
     host: String,
     port: u16,
+    connector: Option<Connector>,
+    connect_timeout: Duration,
+    connect_attempts: u32,
+    connect_backoff: Duration,
+    events: broadcast::Sender<ConnectionEvent>,
+    idle_timeout: Option<Duration>,
+    circuit_breaker: Option<CircuitBreaker>,
+    write_coalescing: Option<Duration>,
+    reserved: [u8; 6],
+    max_body_size: usize,
     state: Arc<Mutex<ClientState>>,
 }
 
 struct ClientState {
     connection: Option<Connection>,
+    connection_id: u64,
     flow_no_counter: u16,
-    response_map: HashMap<u16, String>,
+    response_map: HashMap<u16, BufferedResponse>,
+    /// Number of responses stored so far, orders `response_map` entries
+    received: u64,
+    max_buffered_responses: usize,
+    unsolicited: broadcast::Sender<RbkFrame>,
+    /// Flow numbers of requests still waiting for their response
+    pending: HashSet<u16>,
+    /// Protocol errors of responses rejected by the decoder, reported to
+    /// the requests waiting for them
+    rejected: HashMap<u16, String>,
     notify: Arc<Notify>,
+    /// Fails the requests still waiting on a connection closed by
+    /// [`RbkPortClient::reset`], cleared once the next connection is up
     disposed: bool,
+    /// When the last request was sent
+    last_activity: Instant,
+    /// Connects failed in a row, counted for the circuit breaker
+    connect_failures: u32,
+    /// Frames received without a pending request
+    unmatched: u64,
+    /// Most recent error of a request or connect
+    last_error: Option<String>,
+    /// Read error which ended the connection with the given id, reported
+    /// to the requests still waiting on it
+    read_error: Option<(u64, std::io::ErrorKind, String)>,
+    /// Connects fail fast until then while the circuit breaker is open
+    circuit_open_until: Option<Instant>,
+}
+
+struct BufferedResponse {
+    seq: u64,
+    frame: RbkFrame,
 }
 
 struct Connection {
-    stream: TcpStream,
+    id: u64,
+    /// Frames are flushed right away, or within the write coalescing
+    /// window when enabled
+    writer: BufWriter<WriteHalf<Box<dyn Transport>>>,
+    /// Whether a flush of the coalesced frames is already scheduled
+    flush_scheduled: bool,
     read_task: tokio::task::JoinHandle<()>,
+    /// Closes the connection once idle, if an idle timeout is set
+    idle_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl RbkPortClient {
+    #[cfg(test)]
     pub fn new(host: String, port: u16) -> Self {
+        Self::with_options(host, port, PortOptions::default())
+    }
+
+    pub fn with_options(host: String, port: u16, options: PortOptions) -> Self {
         Self {
             host,
             port,
+            connector: options.connector,
+            connect_timeout: options.connect_timeout,
+            connect_attempts: options.connect_attempts,
+            connect_backoff: options.connect_backoff,
+            events: options.events,
+            idle_timeout: options.idle_timeout,
+            circuit_breaker: options.circuit_breaker,
+            write_coalescing: options.write_coalescing,
+            reserved: options.reserved,
+            max_body_size: options.max_body_size,
             state: Arc::new(Mutex::new(ClientState {
                 connection: None,
+                connection_id: 0,
                 flow_no_counter: 0,
                 response_map: HashMap::new(),
+                rejected: HashMap::new(),
+                received: 0,
+                max_buffered_responses: options.max_buffered_responses,
+                unsolicited: options.unsolicited,
+                pending: HashSet::new(),
                 notify: Arc::new(Notify::new()),
                 disposed: false,
+                last_activity: Instant::now(),
+                connect_failures: 0,
+                circuit_open_until: None,
+                unmatched: 0,
+                last_error: None,
+                read_error: None,
             })),
         }
     }
 
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Number of flow numbers still awaiting a response
+    #[cfg(test)]
+    pub(crate) async fn pending_count(&self) -> usize {
+        self.state.lock().await.pending.len()
+    }
+
+    /// Number of frames received without a pending request
+    #[cfg(test)]
+    pub(crate) async fn unmatched_count(&self) -> u64 {
+        self.state.lock().await.unmatched
+    }
+
+    /// Counters and last error of this port, see
+    /// [`crate::RbkClient::diagnostics`]
+    pub async fn diagnostics(&self) -> PortDiag {
+        let state = self.state.lock().await;
+        PortDiag {
+            unmatched_responses: state.unmatched,
+            pending_requests: state.pending.len(),
+            // Every connection after the first one is a reconnect
+            reconnects: state.connection_id.saturating_sub(1),
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Whether an open connection is held and it was not reset
+    pub async fn is_connected(&self) -> bool {
+        let state = self.state.lock().await;
+        state.connection.is_some() && !state.disposed
+    }
+
+    /// Id of the open connection, `None` while there is none or it was
+    /// reset
+    pub async fn connection_id(&self) -> Option<u64> {
+        let state = self.state.lock().await;
+        match &state.connection {
+            Some(connection) if !state.disposed => Some(connection.id),
+            _ => None,
+        }
+    }
+
+    /// Dial the port unless a connection is already established
+    pub async fn ensure_connected(&self) -> RbkResult<()> {
+        if self.state.lock().await.connection.is_some() {
+            return Ok(());
+        }
+
+        self.connect().await
+    }
+
     pub async fn request(
         &self,
         api_no: u16,
-        req_str: &str,
+        req_body: &[u8],
+        timeout: Duration,
+    ) -> RbkResult<Vec<u8>> {
+        self.request_frame(api_no, req_body, timeout)
+            .await
+            .map(|frame| frame.body)
+    }
+
+    /// Same as [`RbkPortClient::request`], but keeps the header fields of
+    /// the response frame
+    pub async fn request_frame(
+        &self,
+        api_no: u16,
+        req_body: &[u8],
         timeout: Duration,
-    ) -> RbkResult<String> {
-        let result = self.do_request(api_no, req_str, timeout).await;
+    ) -> RbkResult<RbkFrame> {
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+        let result = self.do_request(api_no, req_body, timeout).await;
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_request(api_no, &result, started.elapsed());
 
         // Reset on error
         if let Err(ref e) = result {
@@ -60,140 +289,546 @@ impl RbkPortClient {
                 api_no, e
             );
             self.reset().await;
+            self.state.lock().await.last_error = Some(e.to_string());
         }
 
         result
     }
 
-    //fixme: not cancel-safe due to the timeout
-    async fn do_request(
-        &self,
-        api_no: u16,
-        req_str: &str,
-        timeout: Duration,
-    ) -> RbkResult<String> {
+    /// Write a request without waiting for its response
+    ///
+    /// No flow number is reserved for the request, the response is
+    /// discarded by the read loop when it arrives.
+    pub async fn send(&self, api_no: u16, req_body: &[u8]) -> RbkResult<()> {
+        let result = self.do_send(api_no, req_body).await;
+
+        if let Err(ref e) = result {
+            debug!("Send failed (API {}), resetting client: {:?}", api_no, e);
+            self.reset().await;
+            self.state.lock().await.last_error = Some(e.to_string());
+        }
+
+        result
+    }
+
+    async fn do_send(&self, api_no: u16, req_body: &[u8]) -> RbkResult<()> {
+        self.ensure_connected().await?;
         let mut state = self.state.lock().await;
 
-        if state.disposed {
+        let flow_no = state.next_flow_no();
+        let request_bytes =
+            encode_request(api_no, req_body, flow_no, self.reserved);
+        state.last_activity = Instant::now();
+
+        let Some(conn) = state.connection.as_mut() else {
             return Err(RbkError::Disposed);
-        }
+        };
+
+        self.write_frame(conn, &request_bytes).await.map_err(|e| {
+            error!("Write error for API {}: {}", api_no, e.kind());
+            RbkError::WriteIo {
+                port: self.port,
+                api_no,
+                source: e,
+            }
+        })
+    }
 
-        // Ensure connection
-        if state.connection.is_none() {
-            drop(state);
-            self.connect().await?;
-            state = self.state.lock().await;
+    /// Write a frame and flush it, or schedule a flush at the end of the
+    /// write coalescing window so frames following shortly after go out
+    /// in the same write
+    async fn write_frame(
+        &self,
+        conn: &mut Connection,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        conn.writer.write_all(bytes).await?;
+
+        let Some(window) = self.write_coalescing else {
+            return conn.writer.flush().await;
+        };
+        if !conn.flush_scheduled {
+            conn.flush_scheduled = true;
+            tokio::spawn(flush_coalesced(
+                self.state.clone(),
+                conn.id,
+                window,
+                self.port,
+            ));
         }
 
+        Ok(())
+    }
+
+    async fn do_request(
+        &self,
+        api_no: u16,
+        req_body: &[u8],
+        timeout: Duration,
+    ) -> RbkResult<RbkFrame> {
+        self.ensure_connected().await?;
+        let mut state = self.state.lock().await;
+
         let flow_no = state.next_flow_no();
         let notify = state.notify.clone();
+        state.last_activity = Instant::now();
 
         // Encode and send request
-        let request_bytes = encode_request(api_no, req_str, flow_no);
+        let request_bytes =
+            encode_request(api_no, req_body, flow_no, self.reserved);
 
-        if let Some(ref mut conn) = state.connection {
-            conn.stream.write_all(&request_bytes).await.map_err(|e| {
-                error!("Write error for API {}: {}", api_no, e.kind());
-                RbkError::WriteError(e.to_string())
-            })?;
-        }
+        let Some(connection_id) = state.connection.as_ref().map(|c| c.id)
+        else {
+            return Err(RbkError::Disposed);
+        };
+
+        state.pending.insert(flow_no);
+        // Forgets the flow number if this future is dropped or fails before
+        // the response arrived, so a late response is discarded
+        let mut guard = PendingGuard {
+            state: self.state.clone(),
+            flow_no,
+            armed: true,
+        };
 
+        let conn = state.connection.as_mut().expect("checked above");
+        let written = self.write_frame(conn, &request_bytes).await;
         drop(state);
 
+        written.map_err(|e| {
+            error!("Write error for API {}: {}", api_no, e.kind());
+            RbkError::WriteIo {
+                port: self.port,
+                api_no,
+                source: e,
+            }
+        })?;
+
         // Wait for response with timeout
         tokio::time::timeout(timeout, async {
             loop {
-                notify.notified().await;
-                let mut state = self.state.lock().await;
+                // Register before checking so a response stored in between
+                // still wakes us up
+                let notified = notify.notified();
+                {
+                    let mut state = self.state.lock().await;
 
-                if state.disposed {
-                    return Err(RbkError::Disposed);
-                }
+                    if state.disposed {
+                        return Err(RbkError::Disposed);
+                    }
+
+                    if let Some(response) = state.response_map.remove(&flow_no)
+                    {
+                        state.pending.remove(&flow_no);
+                        guard.armed = false;
+                        return Ok(response.frame);
+                    }
+
+                    if let Some(message) = state.rejected.remove(&flow_no) {
+                        state.pending.remove(&flow_no);
+                        guard.armed = false;
+                        return Err(RbkError::ProtocolError(message));
+                    }
 
-                if let Some(res_str) = state.response_map.remove(&flow_no) {
-                    return Ok(res_str);
+                    // The connection the request was sent on is gone, the
+                    // response will never arrive
+                    if state
+                        .connection
+                        .as_ref()
+                        .is_none_or(|conn| conn.id != connection_id)
+                    {
+                        return Err(match state.read_error {
+                            Some((id, kind, ref message))
+                                if id == connection_id =>
+                            {
+                                RbkError::ReadIo {
+                                    port: self.port,
+                                    source: std::io::Error::new(
+                                        kind,
+                                        message.clone(),
+                                    ),
+                                }
+                            }
+                            _ => RbkError::ConnectionFailed(
+                                "connection closed by robot".to_string(),
+                            ),
+                        });
+                    }
                 }
+                notified.await;
             }
         })
         .await
-        .map_err(|_| RbkError::Timeout)?
+        .map_err(|_| RbkError::ResponseTimeout { api_no })?
     }
 
     async fn connect(&self) -> RbkResult<()> {
-        let addr = format!("{}:{}", self.host, self.port);
-        let stream = tokio::time::timeout(
-            Duration::from_secs(10),
-            TcpStream::connect(&addr),
-        )
-        .await
-        .map_err(|_| RbkError::Timeout)?
-        .map_err(|e| RbkError::ConnectionFailed(e.to_string()))?;
+        self.check_circuit().await?;
+
+        let result = self.dial().await;
+        if let Err(ref e) = result {
+            self.state.lock().await.last_error = Some(e.to_string());
+        }
+        if let Some(breaker) = self.circuit_breaker {
+            let mut state = self.state.lock().await;
+            match result {
+                Ok(()) => {
+                    state.connect_failures = 0;
+                    state.circuit_open_until = None;
+                }
+                Err(_) => {
+                    state.connect_failures += 1;
+                    if state.connect_failures >= breaker.failures {
+                        warn!(
+                            "Port {} failed to connect {} times in a row, \
+                             not dialing it for {:?}",
+                            self.port, state.connect_failures, breaker.cooldown
+                        );
+                        state.circuit_open_until =
+                            Some(Instant::now() + breaker.cooldown);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Fail fast while the circuit breaker is open
+    ///
+    /// Once the cooldown passed a single connect is let through to probe
+    /// the port, concurrent ones keep failing until it finished.
+    async fn check_circuit(&self) -> RbkResult<()> {
+        let Some(breaker) = self.circuit_breaker else {
+            return Ok(());
+        };
+
+        let mut state = self.state.lock().await;
+        let Some(open_until) = state.circuit_open_until else {
+            return Ok(());
+        };
+        if Instant::now() < open_until {
+            return Err(RbkError::CircuitOpen { port: self.port });
+        }
+
+        debug!(
+            "Probing port {} after the circuit breaker cooldown",
+            self.port
+        );
+        state.circuit_open_until = Some(Instant::now() + breaker.cooldown);
+        Ok(())
+    }
+
+    async fn dial(&self) -> RbkResult<()> {
+        // Ports come up one by one after a reboot, give the missing ones a
+        // moment before failing
+        let mut attempt = 1;
+        let mut backoff = self.connect_backoff;
+        let mut failures = Vec::new();
+        let stream = loop {
+            match self.open_once().await {
+                Ok(stream) => break stream,
+                Err(e) if attempt < self.connect_attempts => {
+                    attempt += 1;
+                    debug!(
+                        "Connecting to port {} failed, attempt {} in {:?}: {}",
+                        self.port, attempt, backoff, e
+                    );
+                    let _ = self.events.send(ConnectionEvent::Reconnecting {
+                        port: self.port,
+                        attempt,
+                        error: e.to_string(),
+                    });
+                    failures.push(e);
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                // A single attempt is reported as is
+                Err(e) if failures.is_empty() => return Err(e),
+                Err(e) => {
+                    failures.push(e);
+                    return Err(RbkError::ConnectAttemptsFailed {
+                        port: self.port,
+                        attempts: failures,
+                    });
+                }
+            }
+        };
+
+        let (reader, writer) = tokio::io::split(stream);
+
+        let mut state = self.state.lock().await;
+        if state.connection.is_some() {
+            // Another request won the race, keep its connection
+            return Ok(());
+        }
+
+        state.connection_id += 1;
+        let id = state.connection_id;
+        #[cfg(feature = "metrics")]
+        if id > 1 {
+            crate::metrics::record_reconnect(self.port);
+        }
 
         let state_clone = self.state.clone();
+        let events = self.events.clone();
+        let port = self.port;
+        let max_body_size = self.max_body_size;
         let read_task = tokio::spawn(async move {
-            read_loop(state_clone).await;
+            read_loop(state_clone, reader, id, max_body_size).await;
+            let _ = events.send(ConnectionEvent::Disconnected { port });
         });
 
-        let mut state = self.state.lock().await;
-        state.connection = Some(Connection { stream, read_task });
+        let idle_task = self.idle_timeout.map(|idle_timeout| {
+            tokio::spawn(close_when_idle(
+                self.state.clone(),
+                id,
+                idle_timeout,
+                self.events.clone(),
+                self.port,
+            ))
+        });
+
+        state.connection = Some(Connection {
+            id,
+            writer: BufWriter::new(writer),
+            flush_scheduled: false,
+            read_task,
+            idle_task,
+        });
         state.disposed = false;
+        state.last_activity = Instant::now();
+        let _ = self
+            .events
+            .send(ConnectionEvent::Connected { port: self.port });
 
         Ok(())
     }
 
-    async fn reset(&self) {
+    async fn open_once(&self) -> RbkResult<Box<dyn Transport>> {
+        tokio::time::timeout(self.connect_timeout, self.open())
+            .await
+            .map_err(|_| RbkError::ConnectTimeout { port: self.port })?
+            .map_err(|source| RbkError::ConnectIo {
+                port: self.port,
+                source,
+            })
+    }
+
+    async fn open(&self) -> std::io::Result<Box<dyn Transport>> {
+        match self.connector {
+            Some(ref connector) => connector(self.port).await,
+            None => {
+                let addr = format!("{}:{}", self.host, self.port);
+                let stream = TcpStream::connect(&addr).await?;
+                Ok(Box::new(stream))
+            }
+        }
+    }
+
+    /// Close the connection and fail every request waiting on it with
+    /// [`RbkError::Disposed`]
+    ///
+    /// The client is not closed for good: the next request dials a new
+    /// connection, which `RbkClient::close` relies on to keep the client
+    /// usable after closing it.
+    pub(crate) async fn reset(&self) {
         let mut state = self.state.lock().await;
         state.response_map.clear();
+        state.rejected.clear();
+        state.pending.clear();
         state.disposed = true;
 
-        if let Some(mut conn) = state.connection.take() {
-            conn.read_task.abort();
-            let _ = conn.stream.shutdown().await;
+        if let Some(conn) = state.connection.take() {
+            conn.close().await;
+        }
+
+        state.notify.notify_waiters();
+    }
+}
+
+impl Connection {
+    async fn close(mut self) {
+        self.read_task.abort();
+        if let Some(idle_task) = self.idle_task.take() {
+            idle_task.abort();
         }
+        let _ = self.writer.shutdown().await;
+    }
+}
+
+/// Flush the frames coalesced on a connection once `window` elapsed
+///
+/// A failed flush drops the connection, which fails the requests waiting
+/// on it instead of letting them run into their timeout.
+async fn flush_coalesced(
+    state: Arc<Mutex<ClientState>>,
+    connection_id: u64,
+    window: Duration,
+    port: u16,
+) {
+    tokio::time::sleep(window).await;
+
+    let mut state = state.lock().await;
+    let Some(conn) = state
+        .connection
+        .as_mut()
+        .filter(|conn| conn.id == connection_id)
+    else {
+        return;
+    };
 
+    conn.flush_scheduled = false;
+    if let Err(e) = conn.writer.flush().await {
+        error!("Flushing coalesced frames to port {} failed: {}", port, e);
+        if let Some(conn) = state.connection.take() {
+            conn.close().await;
+        }
         state.notify.notify_waiters();
     }
 }
 
+/// Close the connection once no request was sent on it for `idle_timeout`,
+/// the next request opens a new one
+async fn close_when_idle(
+    state: Arc<Mutex<ClientState>>,
+    connection_id: u64,
+    idle_timeout: Duration,
+    events: broadcast::Sender<ConnectionEvent>,
+    port: u16,
+) {
+    loop {
+        let deadline = state.lock().await.last_activity + idle_timeout;
+        tokio::time::sleep_until(deadline).await;
+
+        let mut state = state.lock().await;
+        if state
+            .connection
+            .as_ref()
+            .is_none_or(|conn| conn.id != connection_id)
+        {
+            return;
+        }
+
+        if state.last_activity + idle_timeout > Instant::now() {
+            continue;
+        }
+        if !state.pending.is_empty() {
+            // Still waiting for a response, look again later
+            state.last_activity = Instant::now();
+            continue;
+        }
+
+        debug!("Closing idle connection to port {}", port);
+        if let Some(mut conn) = state.connection.take() {
+            // Closing aborts this task, so only the read task is aborted here
+            conn.read_task.abort();
+            let _ = conn.writer.shutdown().await;
+        }
+        let _ = events.send(ConnectionEvent::Disconnected { port });
+        return;
+    }
+}
+
 impl Drop for RbkPortClient {
     fn drop(&mut self) {
         // Note: Drop cannot be async in Rust, and proper cleanup requires async operations.
-        // The read task will be automatically aborted when the JoinHandle is dropped.
-        // The TCP connection will be closed when the TcpStream is dropped.
-        // This provides automatic cleanup, though it's not as graceful as calling reset().
+        // Nothing is closed here, clones share the connection. Dropping the JoinHandle
+        // of the read task detaches the task rather than aborting it, and the task
+        // keeps the connection open until the robot closes it. Call reset() to close it.
     }
 }
 
 impl ClientState {
     fn next_flow_no(&mut self) -> u16 {
-        self.flow_no_counter = (self.flow_no_counter + 1) % 512;
+        // Skip flow numbers still in use, unless all of them are
+        for _ in 0..512 {
+            self.flow_no_counter = (self.flow_no_counter + 1) % 512;
+            if !self.pending.contains(&self.flow_no_counter) {
+                break;
+            }
+        }
         self.flow_no_counter
     }
+
+    /// Keep the response for its request, evicting the oldest responses
+    /// not picked up yet if over the cap
+    fn store_response(&mut self, frame: RbkFrame) {
+        while self.response_map.len() >= self.max_buffered_responses.max(1) {
+            let Some(oldest) = self
+                .response_map
+                .iter()
+                .min_by_key(|(_, response)| response.seq)
+                .map(|(flow_no, _)| *flow_no)
+            else {
+                break;
+            };
+            warn!(
+                "Response buffer full ({} entries), dropping response \
+                 (flow_no {})",
+                self.max_buffered_responses, oldest
+            );
+            self.response_map.remove(&oldest);
+        }
+
+        self.received += 1;
+        self.response_map.insert(
+            frame.flow_no,
+            BufferedResponse {
+                seq: self.received,
+                frame,
+            },
+        );
+    }
+
+    fn forget(&mut self, flow_no: u16) {
+        self.pending.remove(&flow_no);
+        self.response_map.remove(&flow_no);
+        self.rejected.remove(&flow_no);
+    }
 }
 
-async fn read_loop(state: Arc<Mutex<ClientState>>) {
-    let mut decoder = RbkDecoder::new();
-    let mut buf = BytesMut::with_capacity(4096);
-    let mut read_buf = vec![0u8; 4096];
+/// Releases the flow number of a request that did not complete
+struct PendingGuard {
+    state: Arc<Mutex<ClientState>>,
+    flow_no: u16,
+    armed: bool,
+}
 
-    loop {
-        // Get a mutable reference to the stream
-        let mut state_lock = state.lock().await;
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
 
-        let has_connection = state_lock.connection.is_some();
-        if !has_connection {
-            break;
+        let flow_no = self.flow_no;
+        match self.state.try_lock() {
+            Ok(mut state) => state.forget(flow_no),
+            Err(_) => {
+                // The state is busy, clean up as soon as it is free
+                let state = self.state.clone();
+                if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                    handle.spawn(async move {
+                        state.lock().await.forget(flow_no);
+                    });
+                }
+            }
         }
+    }
+}
 
-        // Take ownership of the stream temporarily
-        let mut conn = match state_lock.connection.take() {
-            Some(c) => c,
-            None => break,
-        };
-        drop(state_lock);
+async fn read_loop(
+    state: Arc<Mutex<ClientState>>,
+    mut reader: ReadHalf<Box<dyn Transport>>,
+    connection_id: u64,
+    max_body_size: usize,
+) {
+    let mut decoder = RbkDecoder::with_max_body_size(max_body_size);
+    let mut buf = BytesMut::with_capacity(4096);
+    let mut read_buf = vec![0u8; 4096];
 
-        // Read from stream without holding the lock
-        match conn.stream.read(&mut read_buf).await {
+    loop {
+        match reader.read(&mut read_buf).await {
             Ok(0) => {
                 // Connection closed
                 break;
@@ -202,20 +837,686 @@ async fn read_loop(state: Arc<Mutex<ClientState>>) {
                 buf.extend_from_slice(&read_buf[..n]);
 
                 // Process all complete frames
-                while let Some(frame) = decoder.decode(&mut buf) {
-                    let mut state = state.lock().await;
-                    state.response_map.insert(frame.flow_no, frame.body);
-                    state.notify.notify_waiters();
+                loop {
+                    match decoder.decode(&mut buf) {
+                        Ok(Some(frame)) => {
+                            let mut state = state.lock().await;
+                            if !state.pending.contains(&frame.flow_no) {
+                                debug!(
+                                    "Received frame without a pending \
+                                     request (flow_no {}, API {})",
+                                    frame.flow_no, frame.api_no
+                                );
+                                state.unmatched += 1;
+                                // Nobody listening is fine, the frame is
+                                // dropped then
+                                let _ = state.unsolicited.send(frame);
+                                continue;
+                            }
+                            state.store_response(frame);
+                            state.notify.notify_waiters();
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            error!("Dropping frame: {}", e);
+                            // Fail the request waiting for the rejected
+                            // frame instead of letting it run into its
+                            // timeout
+                            let (
+                                Some(flow_no),
+                                RbkError::ProtocolError(message),
+                            ) = (decoder.take_rejected_flow_no(), e)
+                            else {
+                                continue;
+                            };
+                            let mut state = state.lock().await;
+                            if state.pending.contains(&flow_no) {
+                                state.rejected.insert(flow_no, message);
+                                state.notify.notify_waiters();
+                            }
+                        }
+                    }
                 }
-
-                // Put the stream back
-                let mut state = state.lock().await;
-                state.connection = Some(conn);
             }
             Err(e) => {
                 error!("Read error: {}", e);
+                state.lock().await.read_error =
+                    Some((connection_id, e.kind(), e.to_string()));
                 break;
             }
         }
     }
+
+    // Drop the dead connection so that the next request dials again and
+    // wake up pending requests instead of letting them run into the timeout
+    let mut state = state.lock().await;
+    if state
+        .connection
+        .as_ref()
+        .is_some_and(|conn| conn.id == connection_id)
+    {
+        state.connection = None;
+    }
+    state.notify.notify_waiters();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Robot listening on a local port which answers each request, on
+    /// every connection, after `delay` with the body `answer` returns for
+    /// the request's API number and body, or never for `None`
+    ///
+    /// Returns the port and the number of connections accepted so far.
+    async fn answering_robot(
+        delay: Duration,
+        answer: impl Fn(u16, &[u8]) -> Option<Vec<u8>> + Send + Sync + 'static,
+    ) -> (u16, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let accepted = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let answer = Arc::new(answer);
+
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let answer = answer.clone();
+                tokio::spawn(async move {
+                    let mut header = [0u8; 16];
+                    while stream.read_exact(&mut header).await.is_ok() {
+                        let flow_no =
+                            u16::from_be_bytes([header[2], header[3]]);
+                        let len = u32::from_be_bytes([
+                            header[4], header[5], header[6], header[7],
+                        ]);
+                        let api_no = u16::from_be_bytes([header[8], header[9]]);
+                        let mut body = vec![0u8; len as usize];
+                        if stream.read_exact(&mut body).await.is_err() {
+                            break;
+                        }
+
+                        let Some(body) = answer(api_no, &body) else {
+                            continue;
+                        };
+                        tokio::time::sleep(delay).await;
+                        let response = encode_request(
+                            api_no + 10000,
+                            &body,
+                            flow_no,
+                            RESERVED,
+                        );
+                        if stream.write_all(&response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        (port, accepted)
+    }
+
+    #[tokio::test]
+    async fn test_connection_closed_while_pending() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // Accept the request, then hang up without answering
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 64];
+            let _ = stream.read(&mut buf).await;
+        });
+
+        let client = RbkPortClient::new("127.0.0.1".to_string(), port);
+        let started = std::time::Instant::now();
+        let result = client.request(1000, &[], Duration::from_secs(10)).await;
+
+        assert!(
+            matches!(result, Err(RbkError::ConnectionFailed(_))),
+            "Unexpected result: {:?}",
+            result
+        );
+        assert!(
+            started.elapsed() < Duration::from_millis(200),
+            "Closed connection took {:?} to fail",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_does_not_leak() {
+        // Answer every request, but only after a delay
+        let (port, _) = answering_robot(Duration::from_millis(100), |_, _| {
+            Some(b"{}".to_vec())
+        })
+        .await;
+
+        let client = RbkPortClient::new("127.0.0.1".to_string(), port);
+        let request = client.request(1000, &[], Duration::from_secs(10));
+        let dropped =
+            tokio::time::timeout(Duration::from_millis(20), request).await;
+        assert!(dropped.is_err(), "Request should still be pending");
+
+        // Let the late response arrive
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        {
+            let state = client.state.lock().await;
+            assert!(state.pending.is_empty(), "Flow number leaked");
+            assert!(state.response_map.is_empty(), "Late response leaked");
+        }
+
+        // The connection stays usable
+        let result = client.request(1000, &[], Duration::from_secs(1)).await;
+        assert!(result.is_ok(), "Unexpected result: {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_response_buffer_is_capped() {
+        let client = RbkPortClient::with_options(
+            "127.0.0.1".to_string(),
+            0,
+            PortOptions {
+                max_buffered_responses: 8,
+                ..PortOptions::default()
+            },
+        );
+
+        let mut state = client.state.lock().await;
+        for flow_no in 0..100 {
+            state.pending.insert(flow_no);
+            state.store_response(RbkFrame {
+                flow_no,
+                api_no: 11000,
+                body: Vec::new(),
+            });
+            assert!(state.response_map.len() <= 8);
+        }
+
+        // The newest responses are kept
+        let mut kept: Vec<u16> = state.response_map.keys().copied().collect();
+        kept.sort();
+        assert_eq!(kept, (92..100).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_is_closed_and_reopened() {
+        let (port, accepted) =
+            answering_robot(Duration::ZERO, |_, _| Some(b"{}".to_vec())).await;
+
+        let client = RbkPortClient::with_options(
+            "127.0.0.1".to_string(),
+            port,
+            PortOptions {
+                idle_timeout: Some(Duration::from_millis(50)),
+                ..PortOptions::default()
+            },
+        );
+
+        let result = client.request(1000, &[], Duration::from_secs(1)).await;
+        assert!(result.is_ok(), "Unexpected result: {:?}", result);
+        assert!(client.is_connected().await);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(!client.is_connected().await, "Idle connection kept open");
+
+        let result = client.request(1000, &[], Duration::from_secs(1)).await;
+        assert!(result.is_ok(), "Unexpected result: {:?}", result);
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // A port nobody listens on
+        let dials = Arc::new(AtomicUsize::new(0));
+        let counter = dials.clone();
+        let connector: Connector = Arc::new(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {
+                Err(std::io::Error::from(std::io::ErrorKind::ConnectionRefused))
+            })
+        });
+        let client = RbkPortClient::with_options(
+            "unused".to_string(),
+            19204,
+            PortOptions {
+                connector: Some(connector),
+                connect_attempts: 1,
+                circuit_breaker: Some(CircuitBreaker {
+                    failures: 2,
+                    cooldown: Duration::from_millis(100),
+                }),
+                ..PortOptions::default()
+            },
+        );
+        let request = || client.request(1000, &[], Duration::from_secs(1));
+
+        for _ in 0..2 {
+            let result = request().await;
+            assert!(
+                matches!(result, Err(RbkError::ConnectIo { port: 19204, .. })),
+                "{:?}",
+                result
+            );
+        }
+
+        // Threshold reached, no further dialing during the cooldown
+        for _ in 0..3 {
+            let result = request().await;
+            assert!(
+                matches!(result, Err(RbkError::CircuitOpen { port: 19204 })),
+                "{:?}",
+                result
+            );
+        }
+        assert_eq!(dials.load(Ordering::SeqCst), 2);
+
+        // Half-open: a single probe, which fails and opens the breaker again
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let result = request().await;
+        assert!(
+            matches!(result, Err(RbkError::ConnectIo { port: 19204, .. })),
+            "{:?}",
+            result
+        );
+        assert_eq!(dials.load(Ordering::SeqCst), 3);
+        let result = request().await;
+        assert!(matches!(result, Err(RbkError::CircuitOpen { .. })));
+        assert_eq!(dials.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_connect_attempts_are_reported() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Refused twice, then the robot never accepts
+        let dials = Arc::new(AtomicUsize::new(0));
+        let counter = dials.clone();
+        let connector: Connector = Arc::new(move |_| {
+            let dial = counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                if dial < 2 {
+                    Err(std::io::Error::from(
+                        std::io::ErrorKind::ConnectionRefused,
+                    ))
+                } else {
+                    std::future::pending().await
+                }
+            })
+        });
+        let client = RbkPortClient::with_options(
+            "unused".to_string(),
+            19204,
+            PortOptions {
+                connector: Some(connector),
+                connect_timeout: Duration::from_millis(20),
+                connect_attempts: 3,
+                connect_backoff: Duration::from_millis(1),
+                ..PortOptions::default()
+            },
+        );
+
+        let result = client.request(1000, &[], Duration::from_secs(1)).await;
+        let Err(RbkError::ConnectAttemptsFailed { port, attempts }) = result
+        else {
+            panic!("Unexpected result: {:?}", result);
+        };
+        assert_eq!(port, 19204);
+        assert_eq!(dials.load(Ordering::SeqCst), 3);
+        assert!(matches!(
+            attempts.as_slice(),
+            [
+                RbkError::ConnectIo { .. },
+                RbkError::ConnectIo { .. },
+                RbkError::ConnectTimeout { port: 19204 },
+            ]
+        ));
+
+        let message =
+            RbkError::ConnectAttemptsFailed { port, attempts }.to_string();
+        assert!(
+            message.starts_with(
+                "Failed to connect to port 19204 after 3 attempts: "
+            ),
+            "{}",
+            message
+        );
+        assert!(message.ends_with("Timed out connecting to port 19204"));
+    }
+
+    #[tokio::test]
+    async fn test_coalesced_writes_match_responses() {
+        // Echo every request body back under the request's flow number
+        let (port, _) =
+            answering_robot(Duration::ZERO, |_, body| Some(body.to_vec()))
+                .await;
+
+        let client = RbkPortClient::with_options(
+            "127.0.0.1".to_string(),
+            port,
+            PortOptions {
+                write_coalescing: Some(Duration::from_millis(5)),
+                ..PortOptions::default()
+            },
+        );
+        client.ensure_connected().await.unwrap();
+
+        let requests = (0..100u16).map(|i| {
+            let client = &client;
+            async move {
+                let body = format!(r#"{{"seq":{}}}"#, i);
+                let frame = client
+                    .request_frame(
+                        1000 + i,
+                        body.as_bytes(),
+                        Duration::from_secs(5),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(frame.api_no, 11000 + i);
+                assert_eq!(frame.body, body.as_bytes());
+            }
+        });
+        futures_util::future::join_all(requests).await;
+    }
+
+    /// Transport whose reads fail once a request was written, or whose
+    /// writes fail right away
+    struct FailingTransport {
+        fail_write: bool,
+        written: bool,
+        read_waker: Option<std::task::Waker>,
+    }
+
+    impl tokio::io::AsyncRead for FailingTransport {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            _buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            if !self.written {
+                self.read_waker = Some(cx.waker().clone());
+                return std::task::Poll::Pending;
+            }
+            std::task::Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "reset by peer",
+            )))
+        }
+    }
+
+    impl tokio::io::AsyncWrite for FailingTransport {
+        fn poll_write(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            if self.fail_write {
+                return std::task::Poll::Ready(Err(std::io::Error::from(
+                    std::io::ErrorKind::BrokenPipe,
+                )));
+            }
+            self.written = true;
+            if let Some(waker) = self.read_waker.take() {
+                waker.wake();
+            }
+            std::task::Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    fn failing_client(fail_write: bool) -> RbkPortClient {
+        let connector: Connector = Arc::new(move |_| {
+            Box::pin(async move {
+                Ok(Box::new(FailingTransport {
+                    fail_write,
+                    written: false,
+                    read_waker: None,
+                }) as Box<dyn Transport>)
+            })
+        });
+        RbkPortClient::with_options(
+            "unused".to_string(),
+            19204,
+            PortOptions {
+                connector: Some(connector),
+                ..PortOptions::default()
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_io_errors_name_the_operation() {
+        let connector: Connector = Arc::new(|_| {
+            Box::pin(async {
+                Err(std::io::Error::from(std::io::ErrorKind::ConnectionRefused))
+            })
+        });
+        let client = RbkPortClient::with_options(
+            "unused".to_string(),
+            19204,
+            PortOptions {
+                connector: Some(connector),
+                connect_attempts: 1,
+                ..PortOptions::default()
+            },
+        );
+        let err = client
+            .request(1000, &[], Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to connect to port 19204: "),
+            "{}",
+            err
+        );
+
+        let err = failing_client(false)
+            .request(1000, &[], Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RbkError::ReadIo { port: 19204, .. }));
+        assert_eq!(
+            err.to_string(),
+            "Failed to read from port 19204: reset by peer"
+        );
+
+        let err = failing_client(true)
+            .request(1000, &[], Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Failed to write API 1000 to port 19204: "),
+            "{}",
+            err
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: std::sync::Mutex<
+            HashMap<String, Arc<std::sync::atomic::AtomicU64>>,
+        >,
+        histograms: std::sync::Mutex<HashMap<String, Arc<Samples>>>,
+    }
+
+    #[cfg(feature = "metrics")]
+    #[derive(Default)]
+    struct Samples(std::sync::Mutex<Vec<f64>>);
+
+    #[cfg(feature = "metrics")]
+    impl ::metrics::HistogramFn for Samples {
+        fn record(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    impl TestRecorder {
+        fn counter(&self, key: &str) -> u64 {
+            self.counters.lock().unwrap().get(key).map_or(0, |counter| {
+                counter.load(std::sync::atomic::Ordering::SeqCst)
+            })
+        }
+
+        fn samples(&self, key: &str) -> usize {
+            self.histograms
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |samples| samples.0.lock().unwrap().len())
+        }
+
+        fn key(key: &::metrics::Key) -> String {
+            let labels: Vec<_> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            format!("{}{{{}}}", key.name(), labels.join(","))
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    impl ::metrics::Recorder for TestRecorder {
+        fn describe_counter(
+            &self,
+            _: ::metrics::KeyName,
+            _: Option<::metrics::Unit>,
+            _: ::metrics::SharedString,
+        ) {
+        }
+
+        fn describe_gauge(
+            &self,
+            _: ::metrics::KeyName,
+            _: Option<::metrics::Unit>,
+            _: ::metrics::SharedString,
+        ) {
+        }
+
+        fn describe_histogram(
+            &self,
+            _: ::metrics::KeyName,
+            _: Option<::metrics::Unit>,
+            _: ::metrics::SharedString,
+        ) {
+        }
+
+        fn register_counter(
+            &self,
+            key: &::metrics::Key,
+            _: &::metrics::Metadata<'_>,
+        ) -> ::metrics::Counter {
+            let counter = self
+                .counters
+                .lock()
+                .unwrap()
+                .entry(Self::key(key))
+                .or_default()
+                .clone();
+            ::metrics::Counter::from_arc(counter)
+        }
+
+        fn register_gauge(
+            &self,
+            _: &::metrics::Key,
+            _: &::metrics::Metadata<'_>,
+        ) -> ::metrics::Gauge {
+            ::metrics::Gauge::noop()
+        }
+
+        fn register_histogram(
+            &self,
+            key: &::metrics::Key,
+            _: &::metrics::Metadata<'_>,
+        ) -> ::metrics::Histogram {
+            let samples = self
+                .histograms
+                .lock()
+                .unwrap()
+                .entry(Self::key(key))
+                .or_default()
+                .clone();
+            ::metrics::Histogram::from_arc(samples)
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_count_requests_and_reconnects() {
+        let recorder = TestRecorder::default();
+        // The recorder is local to this thread, so the client has to run on
+        // a current thread runtime
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let port = ::metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                // Answer API 1000 only, so API 1001 runs into its timeout
+                let (port, _) = answering_robot(Duration::ZERO, |api_no, _| {
+                    (api_no == 1000).then(|| b"{}".to_vec())
+                })
+                .await;
+
+                let client = RbkPortClient::new("127.0.0.1".to_string(), port);
+                let timeout = Duration::from_secs(5);
+                client.request(1000, &[], timeout).await.unwrap();
+                let result =
+                    client.request(1001, &[], Duration::from_millis(50)).await;
+                assert!(matches!(
+                    result,
+                    Err(RbkError::ResponseTimeout { .. })
+                ));
+                // The failed request dropped the connection
+                client.request(1000, &[], timeout).await.unwrap();
+
+                port
+            })
+        });
+
+        assert_eq!(
+            recorder.counter("rbk_requests_total{api_no=1000,outcome=ok}"),
+            2
+        );
+        assert_eq!(
+            recorder.counter("rbk_requests_total{api_no=1001,outcome=timeout}"),
+            1
+        );
+        assert_eq!(
+            recorder.samples("rbk_request_duration_seconds{api_no=1000}"),
+            2
+        );
+        assert_eq!(
+            recorder.samples("rbk_request_duration_seconds{api_no=1001}"),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!("rbk_reconnects_total{{port={}}}", port)),
+            1
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::error::RbkError;
 use crate::frame::RbkFrame;
 use bytes::{Buf, BufMut, BytesMut};
 
@@ -5,15 +6,17 @@ use bytes::{Buf, BufMut, BytesMut};
 const START_MARK: u8 = 0x5A;
 const PROTO_VERSION: u8 = 0x01;
 const HEAD_SIZE: usize = 16;
-const RESERVED: [u8; 6] = [0; 6];
+/// Reserved header bytes sent unless configured otherwise
+pub(crate) const RESERVED: [u8; 6] = [0; 6];
 
-/// Encode an RBK request into bytes
+/// Encode an RBK request into bytes, `reserved` fills the reserved header
+/// bytes
 pub(crate) fn encode_request(
     api_no: u16,
-    body_str: &str,
+    body_bytes: &[u8],
     flow_no: u16,
+    reserved: [u8; 6],
 ) -> BytesMut {
-    let body_bytes = body_str.as_bytes();
     let body_len = body_bytes.len() as u32;
 
     let mut buf = BytesMut::with_capacity(HEAD_SIZE + body_bytes.len());
@@ -24,7 +27,7 @@ pub(crate) fn encode_request(
     buf.put_u16(flow_no);
     buf.put_u32(body_len);
     buf.put_u16(api_no);
-    buf.put_slice(&RESERVED);
+    buf.put_slice(&reserved);
 
     // Write body
     buf.put_slice(body_bytes);
@@ -32,27 +35,65 @@ pub(crate) fn encode_request(
     buf
 }
 
+/// Largest body accepted by default, generous enough for map transfers
+pub(crate) const DEFAULT_MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
 /// Decoder state for RBK protocol
 pub(crate) struct RbkDecoder {
     started: bool,
     flow_no: u16,
     api_no: u16,
-    body_size: i32,
+    body_size: Option<usize>,
+    max_body_size: usize,
+    /// Bytes of a rejected oversized body still to be discarded
+    skip: usize,
+    /// Flow number of the last rejected frame, not taken yet
+    rejected_flow_no: Option<u16>,
 }
 
 impl RbkDecoder {
+    #[cfg(any(test, feature = "codec"))]
     pub fn new() -> Self {
+        Self::with_max_body_size(DEFAULT_MAX_BODY_SIZE)
+    }
+
+    pub fn with_max_body_size(max_body_size: usize) -> Self {
         Self {
             started: false,
             flow_no: 0,
             api_no: 0,
-            body_size: -1,
+            body_size: None,
+            max_body_size,
+            skip: 0,
+            rejected_flow_no: None,
         }
     }
 
+    /// Flow number of the frame rejected by the last failed
+    /// [`decode`](Self::decode), so its request can be failed
+    pub fn take_rejected_flow_no(&mut self) -> Option<u16> {
+        self.rejected_flow_no.take()
+    }
+
     /// Try to decode a frame from the buffer
-    /// Returns Some(RbkFrame) if a complete frame was decoded, None otherwise
-    pub fn decode(&mut self, buf: &mut BytesMut) -> Option<RbkFrame> {
+    /// Returns Ok(Some(RbkFrame)) if a complete frame was decoded, Ok(None) if
+    /// more data is needed. A header announcing a body larger than the
+    /// configured maximum is rejected with `RbkError::ProtocolError`, the
+    /// decoder then discards the announced body, so start markers within
+    /// it are not mistaken for the next frame.
+    pub fn decode(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<Option<RbkFrame>, RbkError> {
+        if self.skip > 0 {
+            let skipped = self.skip.min(buf.remaining());
+            buf.advance(skipped);
+            self.skip -= skipped;
+            if self.skip > 0 {
+                return Ok(None);
+            }
+        }
+
         // Look for start marker
         if !self.started {
             while buf.has_remaining() {
@@ -63,33 +104,49 @@ impl RbkDecoder {
             }
 
             if !self.started {
-                return None;
+                return Ok(None);
             }
         }
 
         // Read header
-        if self.body_size < 0 {
-            if buf.remaining() < 15 {
-                return None;
-            }
+        let body_size = match self.body_size {
+            Some(body_size) => body_size,
+            None => {
+                if buf.remaining() < HEAD_SIZE - 1 {
+                    return Ok(None);
+                }
 
-            let _version = buf.get_u8();
-            self.flow_no = buf.get_u16();
-            self.body_size = buf.get_u32() as i32;
-            self.api_no = buf.get_u16();
-            buf.advance(6); // Skip reserved bytes
-        }
+                let _version = buf.get_u8();
+                self.flow_no = buf.get_u16();
+                let body_size = buf.get_u32() as usize;
+                self.api_no = buf.get_u16();
+                buf.advance(RESERVED.len());
+
+                if body_size > self.max_body_size {
+                    let api_no = self.api_no;
+                    self.rejected_flow_no = Some(self.flow_no);
+                    self.reset();
+                    self.skip = body_size;
+                    return Err(RbkError::ProtocolError(format!(
+                        "body size {} of API {} exceeds the limit of {} bytes",
+                        body_size, api_no, self.max_body_size
+                    )));
+                }
+
+                self.body_size = Some(body_size);
+                body_size
+            }
+        };
 
         // Read body
-        if buf.remaining() < self.body_size as usize {
-            return None;
+        if buf.remaining() < body_size {
+            return Ok(None);
         }
 
-        let body = if self.body_size == 0 {
-            String::new()
+        let body = if body_size == 0 {
+            Vec::new()
         } else {
-            let body_bytes = buf.split_to(self.body_size as usize);
-            String::from_utf8_lossy(&body_bytes).to_string()
+            buf.split_to(body_size).to_vec()
         };
 
         let frame = RbkFrame {
@@ -99,18 +156,23 @@ impl RbkDecoder {
         };
 
         // Reset state for next frame
+        self.reset();
+
+        Ok(Some(frame))
+    }
+
+    fn reset(&mut self) {
         self.started = false;
         self.flow_no = 0;
         self.api_no = 0;
-        self.body_size = -1;
-
-        Some(frame)
+        self.body_size = None;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_encode_decode() {
@@ -118,14 +180,140 @@ mod tests {
         let body = r#"{"simple": true}"#;
         let flow_no = 42;
 
-        let encoded = encode_request(api_no, body, flow_no);
+        let encoded =
+            encode_request(api_no, body.as_bytes(), flow_no, RESERVED);
         let mut buf = encoded;
 
         let mut decoder = RbkDecoder::new();
-        let frame = decoder.decode(&mut buf).expect("Should decode frame");
+        let frame = decoder
+            .decode(&mut buf)
+            .unwrap()
+            .expect("Should decode frame");
 
         assert_eq!(frame.flow_no, flow_no);
         assert_eq!(frame.api_no, api_no);
-        assert_eq!(frame.body, body);
+        assert_eq!(frame.body, body.as_bytes());
+    }
+
+    #[test]
+    fn test_reject_oversized_body() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(START_MARK);
+        buf.put_u8(PROTO_VERSION);
+        buf.put_u16(7);
+        buf.put_u32(2048);
+        buf.put_u16(1000);
+        buf.put_slice(&RESERVED);
+        // A body full of start markers and a header lookalike
+        let mut body = encode_request(1001, b"", 9, RESERVED).to_vec();
+        body.resize(2048, START_MARK);
+        buf.extend_from_slice(&body[..1000]);
+
+        let mut decoder = RbkDecoder::with_max_body_size(1024);
+        assert!(matches!(
+            decoder.decode(&mut buf),
+            Err(RbkError::ProtocolError(_))
+        ));
+        assert_eq!(decoder.take_rejected_flow_no(), Some(7));
+        assert_eq!(decoder.take_rejected_flow_no(), None);
+        assert!(decoder.decode(&mut buf).unwrap().is_none());
+
+        // The rest of the body arrives together with the next frame
+        buf.extend_from_slice(&body[1000..]);
+        buf.extend_from_slice(&encode_request(1004, b"{}", 8, RESERVED));
+
+        // The decoder skips the body and resyncs to the following frame
+        let frame = decoder
+            .decode(&mut buf)
+            .unwrap()
+            .expect("Should decode frame after resync");
+        assert_eq!(frame.flow_no, 8);
+        assert_eq!(frame.api_no, 1004);
+        assert_eq!(frame.body, b"{}");
+    }
+
+    #[test]
+    fn test_header_is_big_endian() {
+        let encoded = encode_request(0x1234, b"ab", 0xABCD, RESERVED);
+
+        assert_eq!(
+            &encoded[..HEAD_SIZE],
+            &[
+                START_MARK,
+                PROTO_VERSION,
+                0xAB,
+                0xCD,
+                0x00,
+                0x00,
+                0x00,
+                0x02,
+                0x12,
+                0x34,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0
+            ]
+        );
+    }
+
+    /// Bodies biased towards start markers, the bytes most likely to
+    /// confuse framing
+    fn body_strategy() -> impl Strategy<Value = Vec<u8>> {
+        prop::collection::vec(
+            prop_oneof![Just(START_MARK), any::<u8>()],
+            0..512,
+        )
+    }
+
+    fn frame_strategy() -> impl Strategy<Value = (u16, u16, Vec<u8>)> {
+        (any::<u16>(), any::<u16>(), body_strategy())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip((api_no, flow_no, body) in frame_strategy()) {
+            let mut buf = encode_request(api_no, &body, flow_no, RESERVED);
+
+            let frame = RbkDecoder::new()
+                .decode(&mut buf)
+                .unwrap()
+                .expect("Should decode frame");
+
+            prop_assert_eq!(frame.api_no, api_no);
+            prop_assert_eq!(frame.flow_no, flow_no);
+            prop_assert_eq!(frame.body, body);
+            prop_assert!(buf.is_empty());
+        }
+
+        #[test]
+        fn prop_round_trip_concatenated(
+            frames in prop::collection::vec(frame_strategy(), 1..8),
+            chunk in 1usize..64,
+        ) {
+            let mut wire = BytesMut::new();
+            for (api_no, flow_no, body) in &frames {
+                wire.extend_from_slice(&encode_request(
+                    *api_no, body, *flow_no, RESERVED,
+                ));
+            }
+
+            // Feed the stream in small chunks to exercise partial headers
+            // and bodies split across reads
+            let mut decoder = RbkDecoder::new();
+            let mut buf = BytesMut::new();
+            let mut decoded = Vec::new();
+            for piece in wire.chunks(chunk) {
+                buf.extend_from_slice(piece);
+                while let Some(frame) = decoder.decode(&mut buf).unwrap() {
+                    decoded.push((frame.api_no, frame.flow_no, frame.body));
+                }
+            }
+
+            prop_assert_eq!(decoded, frames);
+            prop_assert!(buf.is_empty());
+        }
     }
 }
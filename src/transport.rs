@@ -0,0 +1,20 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Byte stream a port client talks the RBK protocol over
+///
+/// Implemented for every `AsyncRead + AsyncWrite` stream, e.g. a
+/// `TcpStream`, a `UnixStream` bridging to a tunnel or one end of
+/// `tokio::io::duplex` for in-memory tests.
+pub trait Transport: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<T> Transport for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+pub(crate) type ConnectFuture =
+    Pin<Box<dyn Future<Output = io::Result<Box<dyn Transport>>> + Send>>;
+
+/// Opens a transport to the given robot port
+pub(crate) type Connector = Arc<dyn Fn(u16) -> ConnectFuture + Send + Sync>;
@@ -15,6 +15,25 @@ use test_fixtures::MockServerFixture;
 static FIXTURE: Mutex<OnceCell<MockServerFixture>> =
     Mutex::const_new(OnceCell::const_new());
 
+/// Serializes tests driving the mock's relocation state, which is shared
+/// by every client
+static RELOCATION: Mutex<()> = Mutex::const_new(());
+
+/// Serializes tests replacing the mock's navigation task queue
+static NAVIGATION: Mutex<()> = Mutex::const_new(());
+
+/// Serializes tests changing the mock's params
+static PARAMS: Mutex<()> = Mutex::const_new(());
+
+/// Serializes tests taking or releasing the control lock
+static LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Serializes tests engaging the charging relay
+static CHARGING: Mutex<()> = Mutex::const_new(());
+
+/// Serializes tests moving the mock's jack
+static JACK: Mutex<()> = Mutex::const_new(());
+
 #[ctor::dtor]
 fn shutdown_mock_server() {
     let mut lock = FIXTURE.try_lock().expect("Failed to lock FIXTURE");
@@ -43,6 +62,16 @@ async fn create_test_client() -> RbkClient {
     RbkClient::new("localhost")
 }
 
+/// API numbers of all requests the mock server has received so far
+async fn received_apis() -> Vec<u16> {
+    reqwest::get("http://localhost:8080/requests")
+        .await
+        .expect("Failed to query the mock's requests")
+        .json()
+        .await
+        .expect("Failed to parse the mock's requests")
+}
+
 #[tokio::test]
 async fn test_robot_info_query() {
     let client = create_test_client().await;
@@ -122,6 +151,7 @@ async fn test_block_status_query() {
 #[tokio::test]
 async fn test_navigation_commands() {
     let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
 
     // Test navigation to target
     let move_cmd = MoveToTarget::new("test_target");
@@ -168,6 +198,7 @@ async fn test_navigation_commands() {
 #[tokio::test]
 async fn test_jack_operations() {
     let client = create_test_client().await;
+    let _jack = JACK.lock().await;
 
     // Test jack load
     let load_request = LoadJackRequest::new();
@@ -275,6 +306,33 @@ async fn test_nav_status_query() {
     ));
 }
 
+#[tokio::test]
+async fn test_nav_status_keeps_navigation_info() {
+    let client = create_test_client().await;
+
+    let nav_status = client
+        .request(
+            NavStatusRequest::new(GetNavStatus::new()),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("Failed to query nav status");
+
+    // 1020 carries the navigation details, unlike a bare status message
+    assert_eq!(nav_status.move_status_info, "Mock navigation running");
+    assert!(nav_status.create_on.is_some());
+    nav_status.into_result().unwrap();
+
+    assert_ne!(
+        NavStatusRequest::new(GetNavStatus::new())
+            .to_api_request()
+            .api_no(),
+        TaskStatusRequest::new(GetTaskStatus::empty())
+            .to_api_request()
+            .api_no()
+    );
+}
+
 #[tokio::test]
 async fn test_operation_info_query() {
     let client = create_test_client().await;
@@ -308,8 +366,8 @@ async fn test_jack_status_query() {
     );
 
     let status = response.unwrap();
-    // Status message should have a success code
-    assert_eq!(status.code, StatusCode::Success);
+    // Jack status should have a success code
+    assert_eq!(status.code, Some(StatusCode::Success));
 }
 
 #[tokio::test]
@@ -351,6 +409,7 @@ async fn test_multiple_concurrent_requests() {
 #[tokio::test]
 async fn test_move_to_target_with_options() {
     let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
 
     // Create a move command with additional options
     let move_cmd = MoveToTarget::new("target_with_options")
@@ -387,6 +446,7 @@ async fn test_task_status_query() {
 #[tokio::test]
 async fn test_designed_path_navigation() {
     let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
 
     // Create a path with multiple waypoints
     let path = vec![
@@ -405,3 +465,1643 @@ async fn test_designed_path_navigation() {
         response.err()
     );
 }
+
+#[tokio::test]
+async fn test_connect_all_ports() {
+    let client = create_test_client().await;
+
+    let result = client.connect_all().await;
+    assert!(result.is_ok(), "Failed to connect all ports: {:?}", result);
+
+    // Already connected ports are reused
+    let result = client.connect_all().await;
+    assert!(result.is_ok(), "Failed to reconnect ports: {:?}", result);
+
+    let response = client
+        .request(CommonInfoRequest::new(), Duration::from_secs(5))
+        .await;
+    assert!(
+        response.is_ok(),
+        "Request after connect_all failed: {:?}",
+        response.err()
+    );
+}
+
+#[tokio::test]
+async fn test_current_lock_query() {
+    let client = create_test_client().await;
+    let _lock = LOCK.lock().await;
+    let current_lock = || async {
+        client
+            .request(CurrentLockRequest::new(), Duration::from_secs(5))
+            .await
+            .expect("Failed to query current lock")
+            .into_result()
+            .unwrap()
+    };
+
+    client
+        .request(
+            LockControlRequest::new(LockControl::new("lock-query-test")),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to take the lock");
+    let lock = current_lock().await;
+    assert!(lock.locked);
+    assert_eq!(lock.nick_name, "lock-query-test");
+
+    client
+        .request(UnlockControlRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to release the lock");
+    let lock = current_lock().await;
+    assert!(!lock.locked);
+    assert!(lock.nick_name.is_empty());
+}
+
+#[tokio::test]
+async fn test_task_list_names_query() {
+    let client = create_test_client().await;
+    let request = TaskListNamesRequest::new();
+
+    let response = client.request(request, Duration::from_secs(5)).await;
+    assert!(
+        response.is_ok(),
+        "Failed to query task chain list: {:?}",
+        response.err()
+    );
+
+    let names = response.unwrap().into_result().unwrap();
+    assert!(
+        !names.lists.is_empty(),
+        "Task chain list should not be empty"
+    );
+}
+
+#[tokio::test]
+async fn test_modbus_write_then_read() {
+    let client = create_test_client().await;
+
+    let write_request =
+        WriteModbusRequest::new(WriteModbus::new(100, [7, 8, 9]));
+    let write_response = client
+        .request(write_request, Duration::from_secs(5))
+        .await
+        .expect("Failed to write modbus registers");
+    assert!(write_response.into_result().is_ok());
+
+    let read_request = ReadModbusRequest::new(ReadModbus::new(99, 5));
+    let data = client
+        .request(read_request, Duration::from_secs(5))
+        .await
+        .expect("Failed to read modbus registers")
+        .into_result()
+        .unwrap();
+    assert_eq!(data.values, vec![0, 7, 8, 9, 0]);
+}
+
+#[tokio::test]
+async fn test_connection_health() {
+    let client = create_test_client().await;
+
+    let health = client.health().await;
+    assert_eq!(health.len(), 6);
+    assert!(
+        health.values().all(|connected| !connected),
+        "Fresh client should not be connected: {:?}",
+        health
+    );
+
+    let probe = client.health_probe(Duration::from_secs(5)).await;
+    assert!(
+        probe.values().all(|healthy| *healthy),
+        "All ports should answer the probe: {:?}",
+        probe
+    );
+
+    let health = client.health().await;
+    assert!(
+        health.values().all(|connected| *connected),
+        "Probed client should be connected: {:?}",
+        health
+    );
+}
+
+#[tokio::test]
+async fn test_run_sequence() {
+    let client = create_test_client().await;
+
+    let steps: Vec<(Box<dyn DynRequest>, Duration)> = vec![
+        (Box::new(CommonInfoRequest::new()), Duration::from_secs(5)),
+        (
+            Box::new(SetJackHeightRequest::new(SetJackHeight::new(0.1))),
+            Duration::from_secs(5),
+        ),
+        (Box::new(JackStatusRequest::new()), Duration::from_secs(5)),
+    ];
+
+    let results = client.run_sequence(steps).await;
+    assert_eq!(results.len(), 3);
+
+    for result in &results {
+        assert!(result.is_ok(), "Sequence step failed: {:?}", result);
+    }
+
+    let info = results[0].as_ref().unwrap();
+    assert!(info.get("id").is_some(), "Robot info should contain an id");
+}
+
+#[tokio::test]
+async fn test_transparent_data_round_trip() {
+    let client = create_test_client().await;
+
+    // Deliberately not valid UTF-8
+    let payload = vec![0x00, 0xff, 0xfe, 0x80, 0x5a, 0x01, 0xc3, 0x28];
+
+    let write_request = TransparentData::new(payload.clone()).into_request();
+    let write_response = client
+        .request_raw(write_request, Duration::from_secs(5))
+        .await
+        .expect("Failed to write transparent data");
+    assert!(write_response.into_result().is_ok());
+
+    let stored = client
+        .request_raw(QueryTransparentDataRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query transparent data");
+    assert_eq!(stored.data, payload);
+}
+
+#[tokio::test]
+async fn test_roller_operations() {
+    let client = create_test_client().await;
+
+    let load_request = RollerOperationRequest::new(RollerOp::FrontLoad)
+        .with_params(RollerParams::new().with_speed(0.3));
+    assert_eq!(load_request.to_api_request().api_no(), 6055);
+
+    let load_response = client
+        .request(load_request, Duration::from_secs(5))
+        .await
+        .expect("Failed to start roller front load");
+    assert!(load_response.into_result().is_ok());
+
+    let stop_response = client
+        .request(
+            RollerOperationRequest::new(RollerOp::Stop),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("Failed to stop roller");
+    assert!(stop_response.into_result().is_ok());
+}
+
+#[tokio::test]
+async fn test_gnss_queries() {
+    let client = create_test_client().await;
+
+    let status = client
+        .request(GnssStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query GNSS status")
+        .into_result()
+        .unwrap();
+    assert!(status.connected);
+    assert!(status.lat.is_some() && status.lon.is_some());
+
+    let list = client
+        .request(GnssListRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query GNSS devices")
+        .into_result()
+        .unwrap();
+    assert!(!list.devices.is_empty());
+
+    let baudrate_response = client
+        .request(
+            SetGnssBaudrateRequest::new(SetGnssBaudrate::new(115200)),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("Failed to set GNSS baudrate");
+    assert!(baudrate_response.into_result().is_ok());
+}
+
+#[tokio::test]
+async fn test_poll_battery_stream() {
+    use futures_util::StreamExt;
+
+    let client = create_test_client().await;
+
+    let stream = client.poll(
+        BatteryStatusRequest::new(),
+        Duration::from_millis(50),
+        Duration::from_secs(5),
+    );
+    let results: Vec<_> = stream.take(3).collect().await;
+
+    assert_eq!(results.len(), 3);
+    for result in &results {
+        let status = result.as_ref().expect("Battery poll failed");
+        assert!((0.0..=1.0).contains(&status.battery_level));
+    }
+}
+
+#[tokio::test]
+async fn test_poll_zero_interval() {
+    use futures_util::StreamExt;
+
+    let client = create_test_client().await;
+
+    let stream = client.poll(
+        BatteryStatusRequest::new(),
+        Duration::ZERO,
+        Duration::from_secs(5),
+    );
+    let results: Vec<_> = stream.take(2).collect().await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.is_ok()));
+}
+
+#[tokio::test]
+async fn test_navigate_and_wait() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+
+    let status = client
+        .navigate_and_wait(
+            MoveToTarget::new("wait_target"),
+            Duration::from_millis(100),
+            Duration::from_secs(30),
+        )
+        .await
+        .expect("Navigation should finish");
+
+    assert!(
+        status.is_terminal(),
+        "Unexpected final status: {:?}",
+        status
+    );
+}
+
+#[tokio::test]
+async fn test_navigate_and_wait_cancels_on_timeout() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+    let sent_before = received_apis().await.len();
+
+    let result = client
+        .navigate_and_wait_or_cancel(
+            MoveToTarget::new("far_target"),
+            Duration::from_millis(50),
+            Duration::from_millis(200),
+        )
+        .await;
+
+    assert!(matches!(result, Err(RbkError::Timeout)), "{:?}", result);
+    assert!(
+        received_apis().await[sent_before..].contains(&3003),
+        "The navigation was not canceled"
+    );
+}
+
+#[tokio::test]
+async fn test_request_with_meta() {
+    let client = create_test_client().await;
+
+    let meta = client
+        .request_with_meta(BatteryStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query battery with meta");
+
+    assert_eq!(meta.api_no, 1007 + 10000);
+    assert_ne!(meta.flow_no, 0);
+    assert!(meta.raw_body.contains("battery_level"));
+    assert!((0.0..=1.0).contains(&meta.parsed.battery_level));
+}
+
+#[tokio::test]
+async fn test_charge_until() {
+    let client = create_test_client().await;
+    let _charging = CHARGING.lock().await;
+
+    let initial = client
+        .request(BatteryStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query battery");
+    let target = (initial.battery_level + 0.02).min(1.0);
+
+    let status = client
+        .charge_until(
+            target,
+            Duration::from_millis(50),
+            Duration::from_secs(10),
+        )
+        .await
+        .expect("Charging did not reach the target level");
+
+    assert!(status.battery_level >= target);
+}
+
+#[tokio::test]
+async fn test_charge_until_timeout_releases_relay() {
+    let client = create_test_client().await;
+    let _charging = CHARGING.lock().await;
+
+    let result = client
+        .charge_until(
+            1.0,
+            Duration::from_millis(50),
+            Duration::from_millis(200),
+        )
+        .await;
+    assert!(matches!(result, Err(RbkError::Timeout)), "{:?}", result);
+
+    let status = client
+        .request(BatteryStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query battery");
+    assert!(!status.charging, "The charging relay was left engaged");
+}
+
+#[tokio::test]
+async fn test_charge_until_drop_releases_relay() {
+    let client = create_test_client().await;
+    let _charging = CHARGING.lock().await;
+
+    let charge = client.charge_until(
+        1.0,
+        Duration::from_millis(50),
+        Duration::from_secs(60),
+    );
+    tokio::time::timeout(Duration::from_millis(200), charge)
+        .await
+        .expect_err("Charging should still be running");
+
+    // The relay is released in the background
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let status = client
+        .request(BatteryStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query battery");
+    assert!(!status.charging, "The charging relay was left engaged");
+}
+
+#[tokio::test]
+async fn test_charge_until_rejects_bad_level() {
+    let client = create_test_client().await;
+
+    for level in [-0.1, 1.5, f64::NAN] {
+        let result = client
+            .charge_until(
+                level,
+                Duration::from_millis(50),
+                Duration::from_secs(1),
+            )
+            .await;
+        assert!(
+            matches!(result, Err(RbkError::InvalidArgument(_))),
+            "{:?}",
+            result
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_configure_push() {
+    let client = create_test_client().await;
+
+    let config = PushConfig::new(19301)
+        .with_apis([1004, 1007])
+        .with_interval(200);
+    let response = client
+        .request(config.into_request(), Duration::from_secs(5))
+        .await;
+
+    assert!(
+        response.is_ok(),
+        "Failed to configure push: {:?}",
+        response.err()
+    );
+    response.unwrap().into_result().unwrap();
+}
+
+#[tokio::test]
+async fn test_task_status_query_modes() {
+    let client = create_test_client().await;
+
+    let package = client
+        .request(
+            TaskStatusRequest::new(GetTaskStatus::empty()),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("Failed to query task status");
+    assert!(package.tasks.is_empty());
+
+    let response = client
+        .request(
+            TaskStatusRequest::new(GetTaskStatus::recent()),
+            Duration::from_secs(5),
+        )
+        .await;
+    assert!(
+        response.is_ok(),
+        "Failed to query recent tasks: {:?}",
+        response.err()
+    );
+}
+
+#[tokio::test]
+async fn test_third_party_alarms() {
+    let client = create_test_client().await;
+
+    let alarm_codes = |body: &str, kind: &str| -> Vec<u64> {
+        let value: serde_json::Value = serde_json::from_str(body).unwrap();
+        value[kind]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|a| a["code"].as_u64())
+            .collect()
+    };
+
+    client
+        .request(
+            ThirdPartyError::new(70001, "Light curtain tripped")
+                .with_level(2)
+                .into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    client
+        .request(
+            SetThirdPartyWarningRequest::new(ThirdPartyError::new(
+                70002,
+                "Door open",
+            )),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let alarms = client
+        .request_with_meta(
+            RobotAlarmStatusRequest::new(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(alarm_codes(&alarms.raw_body, "errors").contains(&70001));
+    assert!(alarm_codes(&alarms.raw_body, "warnings").contains(&70002));
+
+    client
+        .request(
+            ClearThirdPartyErrorRequest::new(ClearThirdPartyError::new(70001)),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    client
+        .request(
+            ClearThirdPartyWarningRequest::new(ClearThirdPartyError::new(
+                70002,
+            )),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let alarms = client
+        .request_with_meta(
+            RobotAlarmStatusRequest::new(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(!alarm_codes(&alarms.raw_body, "errors").contains(&70001));
+    assert!(!alarm_codes(&alarms.raw_body, "warnings").contains(&70002));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_client() {
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(ensure_mock_server());
+
+    let client = BlockingRbkClient::new("localhost").unwrap();
+
+    let status = client
+        .request(BatteryStatusRequest::new(), Duration::from_secs(5))
+        .expect("Blocking battery query failed");
+    assert!((0.0..=1.0).contains(&status.battery_level));
+
+    let info = client
+        .request(CommonInfoRequest::new(), Duration::from_secs(5))
+        .expect("Blocking info query failed");
+    assert!(!info.id.is_empty());
+}
+
+#[tokio::test]
+async fn test_clear_weight() {
+    let client = create_test_client().await;
+
+    let response = client
+        .request(ClearWeightRequest::new(), Duration::from_secs(5))
+        .await;
+    assert!(
+        response.is_ok(),
+        "Failed to clear weight sensor: {:?}",
+        response.err()
+    );
+    response.unwrap().into_result().unwrap();
+}
+
+#[tokio::test]
+async fn test_container_goods_binding() {
+    let client = create_test_client().await;
+    let goods_of = |status: AllStatus1| {
+        status
+            .containers
+            .into_iter()
+            .find(|c| c.container == "test_container")
+            .map(|c| c.goods)
+    };
+
+    client
+        .request(
+            SetContainerGoods::new("test_container", "pallet_42")
+                .into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to bind goods");
+
+    let status = client
+        .request(RobotAllStatus1Request::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(goods_of(status).as_deref(), Some("pallet_42"));
+
+    client
+        .request(
+            ClearGoods::new("pallet_42").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to unbind bound goods");
+
+    let status = client
+        .request(RobotAllStatus1Request::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(goods_of(status), None);
+
+    // Nothing is bound anymore
+    let response = client
+        .request(
+            ClearContainer::new("test_container").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(response.into_result().is_err());
+
+    client
+        .request(
+            ClearAllContainersGoodsRequest::new(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to unbind all goods");
+}
+
+#[tokio::test]
+async fn test_lidar_scan() {
+    let client = create_test_client().await;
+
+    let scan = client
+        .request(RobotLidarDataRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query lidar data")
+        .into_result()
+        .unwrap();
+
+    let beams = ((scan.angle_max - scan.angle_min) / scan.angle_increment)
+        .round() as usize
+        + 1;
+    assert_eq!(scan.ranges.len(), beams);
+    assert_eq!(scan.intensities.len(), beams);
+    assert!((scan.angle_of(beams - 1) - scan.angle_max).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_bin_detection() {
+    let client = create_test_client().await;
+
+    let bins = client
+        .request(RobotBinsRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query bins")
+        .into_result()
+        .unwrap();
+    assert!(!bins.bins.is_empty());
+
+    let result = client
+        .request(
+            BinDetect::new()
+                .with_bin_id(bins.bins[0].bin_id.clone())
+                .into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("Failed to detect bin")
+        .into_result()
+        .unwrap();
+    assert!(result.detected);
+    assert!(result.offset_x.abs() < 0.1);
+
+    let result = client
+        .request(
+            BinDetect::new().with_bin_id("missing_bin").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(!result.detected);
+
+    client
+        .request(ReplayRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_response_parse_error_includes_body() {
+    /// Robot info query expecting a field the robot never sends
+    struct DriftedInfoRequest;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct DriftedInfo {
+        serial_number: String,
+    }
+
+    impl ToRequestBody for DriftedInfoRequest {
+        fn to_request_body(
+            &self,
+        ) -> Result<std::borrow::Cow<'static, str>, serde_json::Error> {
+            Ok(std::borrow::Cow::Borrowed(""))
+        }
+
+        fn to_api_request(&self) -> ApiRequest {
+            ApiRequest::State(StateApi::Info)
+        }
+    }
+
+    impl FromResponseBody for DriftedInfoRequest {
+        type Response = DriftedInfo;
+    }
+
+    let client = create_test_client().await;
+    let result = client
+        .request(DriftedInfoRequest, Duration::from_secs(5))
+        .await;
+
+    match result {
+        Err(RbkError::ResponseParseError { api_no, body, .. }) => {
+            assert_eq!(api_no, 1000);
+            assert!(body.contains("\"id\""), "Unexpected body: {}", body);
+        }
+        other => panic!("Expected a response parse error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_clear_motor_encoders() {
+    let client = create_test_client().await;
+
+    let status = client
+        .request(RobotEncoderStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query encoders")
+        .into_result()
+        .unwrap();
+    assert!(!status.encoders.is_empty());
+
+    client
+        .request(ClearMotorEncoderRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to clear encoders");
+
+    let status = client
+        .request(RobotEncoderStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(!status.encoders.is_empty());
+    assert!(status.encoders.iter().all(|encoder| encoder.value == 0));
+}
+
+#[tokio::test]
+async fn test_calibration_workflow() {
+    let client = create_test_client().await;
+
+    client
+        .request(
+            StartCalibration::new(2).into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to start calibration");
+
+    let status = client
+        .request(CalibrationStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_eq!(status.state, CalibrationState::Running);
+    assert_eq!(status.calib_type, Some(2));
+
+    let mut state = status.state;
+    for _ in 0..30 {
+        if state != CalibrationState::Running {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        state = client
+            .request(CalibrationStatusRequest::new(), Duration::from_secs(5))
+            .await
+            .unwrap()
+            .state;
+    }
+    assert_eq!(state, CalibrationState::Finished);
+
+    let result = client
+        .request(CalibrationResultRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.data["calib_type"], 2);
+
+    // A cancelled calibration never finishes
+    client
+        .request(
+            StartCalibration::new(3).into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    client
+        .request(CancelCalibrationRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    let status = client
+        .request(CalibrationStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(status.state, CalibrationState::Idle);
+}
+
+#[tokio::test]
+async fn test_script_management() {
+    let client = create_test_client().await;
+    let source = "-- blink the ambient lamp\nprint('hello')";
+
+    client
+        .request(
+            UploadScript::new("test_blink.lua", source).into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to upload script");
+
+    let list = client
+        .request(ScriptListRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(list.scripts.contains(&"test_blink.lua".to_string()));
+
+    let script = client
+        .request(
+            ScriptName::new("test_blink.lua").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to download script");
+    assert_eq!(script.content, source);
+
+    client
+        .request(
+            RemoveScriptRequest::new(ScriptName::new("test_blink.lua")),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to remove script");
+
+    let list = client
+        .request(ScriptListRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert!(!list.scripts.contains(&"test_blink.lua".to_string()));
+}
+
+#[tokio::test]
+async fn test_sound_status() {
+    let client = create_test_client().await;
+
+    client
+        .request(
+            PlayAudio::new("welcome.wav").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to play audio");
+
+    let status = client
+        .request(RobotSoundStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(status.playing);
+    assert_eq!(status.current.as_deref(), Some("welcome.wav"));
+    assert!((0.0..=1.0).contains(&status.volume));
+
+    client
+        .request(StopAudioRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let status = client
+        .request(RobotSoundStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert!(!status.playing);
+    assert_eq!(status.current, None);
+}
+
+#[tokio::test]
+async fn test_relocate_and_confirm() {
+    let client = create_test_client().await;
+    let _relocation = RELOCATION.lock().await;
+
+    client
+        .request(RelocateRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to relocate");
+
+    let status = client
+        .request(RobotRelocationStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_ne!(status.state, RelocState::Failed);
+
+    let result = client
+        .confirm_location(Duration::from_secs(5))
+        .await
+        .expect("Failed to confirm location");
+    assert!(result.confirmed);
+    let confidence = result.confidence.expect("Confidence missing");
+    assert!((0.0..=1.0).contains(&confidence));
+}
+
+#[tokio::test]
+async fn test_target_path() {
+    let client = create_test_client().await;
+
+    let path = client
+        .request(
+            GetTargetPath::new("LM1").into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query target path");
+
+    assert!(path.points.len() >= 2, "Path too short: {:?}", path.points);
+    let first = path.points.first().unwrap();
+    let last = path.points.last().unwrap();
+    let straight = (last[0] - first[0]).hypot(last[1] - first[1]);
+    assert!(path.total_distance >= straight - 1e-9);
+}
+
+#[tokio::test]
+async fn test_send_no_reply() {
+    let client = create_test_client().await;
+
+    for _ in 0..100 {
+        client
+            .send_no_reply(OpenLoopMotionRequest::new())
+            .await
+            .expect("Failed to send jog command");
+    }
+
+    // The discarded replies do not disturb regular requests
+    let response = client
+        .request(OpenLoopMotionRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert!(response.into_result().is_ok());
+}
+
+#[tokio::test]
+async fn test_tag3d_mapping() {
+    let client = create_test_client().await;
+
+    client
+        .request(Tag3dMappingRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to start tag mapping");
+
+    let data = client
+        .request(Tag3dRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query 3D tags");
+    assert!(!data.tags.is_empty());
+    assert!(data.tags.iter().all(|tag| !tag.id.is_empty()));
+}
+
+#[tokio::test]
+async fn test_arm_move_and_status() {
+    let client = create_test_client().await;
+    let joints = vec![0.1, -0.5, 1.2, 0.0, 0.7, -1.0];
+
+    client
+        .request(
+            ArmMove::new(joints.clone()).with_speed(0.5).into_request(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to move arm");
+
+    let status = client
+        .request(ArmStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query arm status");
+    assert_eq!(status.joints, joints);
+    assert!(!status.moving);
+    assert_eq!(status.error_code, 0);
+}
+
+#[tokio::test]
+async fn test_query_all_snapshot_round_trip() {
+    let client = create_test_client().await;
+
+    let snapshot = client
+        .query_all(Duration::from_secs(5))
+        .await
+        .expect("Failed to query snapshot");
+    assert_eq!(snapshot.version, RobotSnapshot::VERSION);
+    assert!(snapshot.timestamp_ms > 0);
+
+    let line = serde_json::to_string(&snapshot).unwrap();
+    assert!(!line.contains('\n'));
+    let replayed: RobotSnapshot = serde_json::from_str(&line).unwrap();
+    assert_eq!(replayed, snapshot);
+}
+
+#[tokio::test]
+async fn test_download_model_file() {
+    let client = create_test_client().await;
+
+    let file = client
+        .request_raw(DownloadModelRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to download model file");
+    let model = file.parse().expect("Model file is not valid JSON");
+
+    assert_eq!(model.contour.len(), 4);
+    for [x, y] in &model.contour {
+        assert_eq!(x.abs(), 0.5);
+        assert_eq!(y.abs(), 0.3);
+    }
+
+    // Uploading the same file back leaves it unchanged
+    client
+        .request_raw(
+            UploadModelRequest::new(file.data.clone()),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to upload model file");
+}
+
+#[tokio::test]
+async fn test_clear_odometry() {
+    let client = create_test_client().await;
+
+    let before = client
+        .request(OperationInfoRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query operation info");
+
+    client
+        .request(ClearOdometryRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to clear odometry");
+
+    // The mock keeps counting, so only a fresh start is checked
+    let after = client
+        .request(OperationInfoRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(after.mileage <= before.mileage);
+    assert!(after.total_time_ms < 5000.0, "{}", after.total_time_ms);
+}
+
+#[tokio::test]
+async fn test_ping() {
+    let client = create_test_client().await;
+    let timeout = Duration::from_secs(5);
+
+    let rtt = client.ping(timeout).await.expect("Ping failed");
+    assert!(rtt > Duration::ZERO);
+    assert!(rtt < timeout);
+
+    let all = client.ping_all(timeout).await;
+    assert_eq!(all.len(), 6);
+    for (port, rtt) in all {
+        let rtt =
+            rtt.unwrap_or_else(|e| panic!("Ping of {} failed: {}", port, e));
+        assert!(rtt < timeout);
+    }
+}
+
+impl_api_request!(
+    UnsupportedRequest,
+    ApiRequest::Custom(1998),
+    res: StatusMessage
+);
+
+#[tokio::test]
+async fn test_batch() {
+    let client = create_test_client().await;
+
+    let results = client
+        .batch()
+        .push(CommonInfoRequest::new())
+        .push(UnsupportedRequest::new())
+        .push(RobotPoseRequest::new())
+        .timeout(Duration::from_secs(5))
+        .run()
+        .await;
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+    assert!(
+        matches!(results[1], Err(RbkError::BadResponse { .. })),
+        "{:?}",
+        results[1]
+    );
+    assert!(results[2].is_ok(), "{:?}", results[2]);
+
+    let results = client
+        .batch()
+        .push(CommonInfoRequest::new())
+        .push(UnsupportedRequest::new())
+        .push(RobotPoseRequest::new())
+        .abort_on_error(true)
+        .run()
+        .await;
+    assert_eq!(results.len(), 2, "Steps after the failure were sent");
+    assert!(results[1].is_err());
+}
+
+#[tokio::test]
+async fn test_push_config_tracking() {
+    let client = create_test_client().await;
+    assert_eq!(client.push_config(), None);
+
+    let config = PushConfig::new(19301)
+        .with_apis([1004, 1007, 1020])
+        .with_interval(500);
+    client
+        .configure_push(config.clone(), Duration::from_secs(5))
+        .await
+        .expect("Failed to configure push");
+
+    assert_eq!(client.push_config(), Some(config));
+}
+
+#[tokio::test]
+async fn test_all_status3_query() {
+    let client = create_test_client().await;
+
+    let status = client
+        .request(RobotAllStatus3Request::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query all status 3");
+
+    assert!(status.jack.is_some(), "Jack section missing");
+    assert!(status.fork.is_some(), "Fork section missing");
+    assert_eq!(status.inputs.len(), 8);
+    assert_eq!(status.outputs.len(), 8);
+    status.into_result().unwrap();
+}
+
+#[tokio::test]
+async fn test_auto_lock() {
+    ensure_mock_server().await;
+    let _lock = LOCK.lock().await;
+    let client = RbkClient::builder("localhost")
+        .auto_lock("auto-lock-test")
+        .build();
+
+    // A control command without locking explicitly
+    client
+        .request(ClearWeightRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to clear the weight sensor")
+        .into_result()
+        .unwrap();
+
+    let lock = client
+        .request(CurrentLockRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(lock.locked);
+    assert_eq!(lock.nick_name, "auto-lock-test");
+
+    client.close().await.unwrap();
+    let lock = client
+        .request(CurrentLockRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert!(!lock.locked);
+}
+
+#[tokio::test]
+async fn test_request_as_value() {
+    let client = create_test_client().await;
+
+    let battery = client
+        .request(AsValue(BatteryStatusRequest::new()), Duration::from_secs(5))
+        .await
+        .expect("Failed to query battery status");
+
+    let level = battery["battery_level"]
+        .as_f64()
+        .expect("battery_level is not a number");
+    assert!((0.0..=1.0).contains(&level));
+    assert_eq!(battery["ret_code"], 0);
+}
+
+#[tokio::test]
+async fn test_cancel_relocation() {
+    let client = create_test_client().await;
+    let _relocation = RELOCATION.lock().await;
+
+    client
+        .request(RelocateRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to relocate");
+
+    client
+        .request(CancelRelocationRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to cancel relocation");
+
+    let status = client
+        .request(RobotRelocationStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_eq!(status.state, RelocState::Failed);
+}
+
+#[tokio::test]
+async fn test_wait_for_task() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+
+    let path = MoveDesignedPath::new(vec![
+        MoveToTarget::new("wait_task_target")
+            .with_task_id("wait_for_task_1".to_string()),
+    ]);
+    client
+        .request(MoveDesignedPathRequest::new(path), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to start the task");
+
+    let status = client
+        .wait_for_task(
+            "wait_for_task_1",
+            Duration::from_millis(100),
+            Duration::from_secs(30),
+        )
+        .await
+        .expect("Task should finish");
+
+    // Other tests may replace the mock's task queue meanwhile
+    assert!(
+        matches!(status, TaskStatus::Completed | TaskStatus::NotFound),
+        "Unexpected final status: {:?}",
+        status
+    );
+
+    let status = client
+        .wait_for_task(
+            "no_such_task",
+            Duration::from_millis(100),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert_eq!(status, TaskStatus::NotFound);
+}
+
+#[tokio::test]
+async fn test_list_files() {
+    let client = create_test_client().await;
+
+    let list = client
+        .request(ListFilesRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to list files");
+    let entry = list
+        .files
+        .iter()
+        .find(|file| file.name == "robot.log")
+        .expect("robot.log is not listed");
+    assert_eq!(entry.size, 20_480);
+}
+
+#[tokio::test]
+async fn test_safe_clear_movements() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+
+    let ids = ["safe_clear_1", "safe_clear_2", "safe_clear_3"];
+    let path = MoveDesignedPath::new(ids.iter().enumerate().map(|(i, id)| {
+        MoveToTarget::new(format!("safe_clear_target_{}", i))
+            .with_task_id(id.to_string())
+    }));
+    client
+        .request(MoveDesignedPathRequest::new(path), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to start the path");
+
+    // Clearing the running task moves on to the next one
+    client
+        .request(
+            SafeClearMovementsRequest::new(SafeClearMovements::new(ids[0])),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to clear the task");
+
+    let status = client
+        .request(
+            TaskStatusRequest::new(GetTaskStatus::for_ids(ids)),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    let status_of = |id: &str| {
+        status
+            .tasks
+            .iter()
+            .find(|task| task.task_id == id)
+            .map(|task| task.status)
+    };
+    assert_eq!(status_of(ids[0]), Some(TaskStatus::Canceled));
+    assert_eq!(status_of(ids[1]), Some(TaskStatus::Running));
+    assert_eq!(status_of(ids[2]), Some(TaskStatus::Waiting));
+
+    // Clearing a task twice is refused
+    let response = client
+        .request(
+            SafeClearMovementsRequest::new(SafeClearMovements::new(ids[0])),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert!(response.into_result().is_err());
+}
+
+#[tokio::test]
+async fn test_diagnostics_bundle() {
+    let client = create_test_client().await;
+
+    let bundle = client.diagnostics_bundle(Duration::from_secs(5)).await;
+
+    let imu = bundle.imu.expect("IMU query failed");
+    assert!((imu.acc_z - 9.81).abs() < 1e-9);
+    let encoders = bundle.encoders.expect("Encoder query failed");
+    let motors = bundle.motors.expect("Motor query failed");
+    assert_eq!(encoders.encoders.len(), 2);
+    assert_eq!(motors.motors.len(), encoders.encoders.len());
+    assert_eq!(motors.motors[0].motor_name, "motor0");
+}
+
+#[tokio::test]
+async fn test_reload_params_reverts_temporary_changes() {
+    let client = create_test_client().await;
+    let _params = PARAMS.lock().await;
+    let max_speed = || async {
+        let params = client
+            .request(AsValue(RobotParamsRequest::new()), Duration::from_secs(5))
+            .await
+            .expect("Failed to query params");
+        params["MoveFactory"]["maxSpeed"].as_f64()
+    };
+    let saved = max_speed().await.expect("maxSpeed is not set");
+
+    client
+        .request(
+            SetParamsRequest::new(SetParams::new().with_param(
+                "MoveFactory",
+                "maxSpeed",
+                saved / 2.0,
+            )),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to set params");
+    assert_eq!(max_speed().await, Some(saved / 2.0));
+
+    client
+        .request(ReloadParamsRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to reload params");
+    assert_eq!(max_speed().await, Some(saved));
+}
+
+#[tokio::test]
+async fn test_emergency_stop_all() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+    let _jack = JACK.lock().await;
+    let sent_before = received_apis().await.len();
+
+    let results = client.emergency_stop_all().await;
+    assert_eq!(results.len(), 4);
+    for result in &results {
+        assert!(result.is_ok(), "Stop command failed: {:?}", result);
+    }
+
+    let received = received_apis().await;
+    for api_no in [6004, 3003, 2000, 6072] {
+        assert!(
+            received[sent_before..].contains(&api_no),
+            "API {} was not sent",
+            api_no
+        );
+    }
+
+    let emergency = client
+        .request(
+            AsValue(RobotEmergencyStatusRequest::new()),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+    assert_eq!(emergency["soft_emc"], true);
+
+    client
+        .request(
+            SoftEmcRequest::new(SoftEmc::new(false)),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to release the soft estop");
+}
+
+#[tokio::test]
+async fn test_all_status1_and_2_queries() {
+    let client = create_test_client().await;
+
+    let status = client
+        .request(RobotAllStatus1Request::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query all status 1");
+    assert!(status.pose.is_some(), "Pose section missing");
+    let battery = status.battery.expect("Battery section missing");
+    assert!((0.0..=1.0).contains(&battery.battery_level));
+    assert!(status.block.is_some(), "Block section missing");
+    assert!(status.timestamp.is_some());
+
+    let status = client
+        .request(RobotAllStatus2Request::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query all status 2");
+    assert!(status.speed.is_some(), "Speed section missing");
+    assert!(status.nav.is_some(), "Navigation section missing");
+    assert_eq!(
+        status.area.expect("Area section missing").area_ids,
+        ["mock_area"]
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_navigation_through_handle() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+
+    let navigation = client
+        .start_navigation(MoveToTarget::new("handle_far_target"))
+        .await
+        .expect("Failed to start navigation")
+        .poll_interval(Duration::from_millis(100));
+
+    // Waiting from another task while the navigation is cancelled here
+    let waiter = {
+        let navigation = navigation.clone();
+        tokio::spawn(async move {
+            navigation.await_completion(Duration::from_secs(5)).await
+        })
+    };
+    navigation
+        .cancel()
+        .await
+        .expect("Failed to cancel navigation");
+
+    let status = waiter.await.unwrap().expect("Navigation did not finish");
+    assert_eq!(status, TaskStatus::Canceled);
+}
+
+#[tokio::test]
+async fn test_run_transport() {
+    let client = create_test_client().await;
+    let _navigation = NAVIGATION.lock().await;
+    let _jack = JACK.lock().await;
+
+    let result = client
+        .run_transport(
+            TransportJob::new("transport_pickup", "transport_dropoff")
+                .poll_interval(Duration::from_millis(100))
+                .step_timeout(Duration::from_secs(30)),
+        )
+        .await;
+
+    assert!(result.is_complete(), "Transport failed: {:?}", result);
+    let steps: Vec<TransportStep> =
+        result.steps.iter().map(|(step, _)| *step).collect();
+    assert_eq!(
+        steps,
+        [
+            TransportStep::MoveToPickup,
+            TransportStep::Load,
+            TransportStep::MoveToDropoff,
+            TransportStep::Unload,
+        ]
+    );
+
+    // The load was dropped off and the jack is down
+    let jack = client
+        .request(JackStatusRequest::new(), Duration::from_secs(5))
+        .await
+        .expect("Failed to query jack status");
+    assert!(!jack.has_payload);
+    assert_eq!(jack.operation, JackOperationStatus::LoweringInPlace);
+}
+
+#[tokio::test]
+async fn test_area_speed_limit() {
+    let client = create_test_client().await;
+    let _params = PARAMS.lock().await;
+
+    client
+        .set_area_speed_limit("AreaSpeedLimit", "mock_area", 0.3)
+        .await
+        .expect("Failed to set area speed limit");
+
+    let area = client
+        .request(RobotCurrentAreaRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to query area");
+    assert_eq!(area.area_ids, ["mock_area"]);
+    assert_eq!(area.max_speed, Some(0.3));
+
+    // The limit is temporary, reloading the params lifts it
+    client
+        .request(ReloadParamsRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap()
+        .into_result()
+        .expect("Failed to reload params");
+    let area = client
+        .request(RobotCurrentAreaRequest::new(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(area.max_speed, None);
+}
+
+#[tokio::test]
+async fn test_area_speed_limit_rejects_bad_speed() {
+    let client = create_test_client().await;
+    let _params = PARAMS.lock().await;
+    let sent_before = received_apis().await.len();
+
+    for speed in [0.0, -0.3, f64::NAN, f64::INFINITY] {
+        let result = client
+            .set_area_speed_limit("AreaSpeedLimit", "mock_area", speed)
+            .await;
+        assert!(
+            matches!(result, Err(RbkError::InvalidArgument(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    let received = received_apis().await;
+    assert!(!received[sent_before..].contains(&4100));
+}
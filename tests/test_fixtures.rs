@@ -31,7 +31,7 @@ impl MockServerFixture {
             println!("Starting mock server for tests...");
 
             let process = Command::new("cargo")
-                .args(&["run", "--example", "mock_robot_server"])
+                .args(["run", "--example", "mock_robot_server"])
                 .spawn()
                 .expect("Failed to start mock server");
 
@@ -68,12 +68,11 @@ impl MockServerFixture {
 
 impl Drop for MockServerFixture {
     fn drop(&mut self) {
-        if self.auto_started {
-            if let Some(mut process) = self.process.take() {
-                println!("Stopping mock server...");
-                let _ = process.kill();
-                let _ = process.wait();
-            }
+        if self.auto_started
+            && let Some(mut process) = self.process.take()
+        {
+            println!("Stopping mock server...");
+            let _ = process.start_kill();
         }
     }
 }